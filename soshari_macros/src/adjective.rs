@@ -0,0 +1,221 @@
+use std::collections::HashSet;
+
+use proc_macro2::{Ident, Span};
+use syn::{
+    braced, parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    token, Error, Lit, MetaNameValue, Result, Token,
+};
+
+/// Discord's limit on select menu option labels and descriptions.
+const MAX_LENGTH: usize = 100;
+
+/// A single entry of an adjective list, either a bare `ident` or
+/// `ident = { label = "...", description = "...", emoji = "..." }`.
+pub struct Adjective {
+    pub ident: Ident,
+    pub label: Option<String>,
+    pub description: Option<String>,
+    pub emoji: Option<String>,
+}
+
+impl Adjective {
+    /// The name the adjective is matched and stored by, e.g. `self-assertive`.
+    pub fn name(&self) -> String {
+        self.ident.to_string().replace('_', "-")
+    }
+
+    /// The name shown to users, defaulting to [`Adjective::name`].
+    pub fn label(&self) -> String {
+        self.label.clone().unwrap_or_else(|| self.name())
+    }
+
+    pub fn upper_ident(&self) -> Ident {
+        Ident::new(&self.ident.to_string().to_uppercase(), self.ident.span())
+    }
+}
+
+impl Parse for Adjective {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut adjective = Adjective {
+            ident: input.parse()?,
+            label: None,
+            description: None,
+            emoji: None,
+        };
+
+        if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            let content;
+            braced!(content in input);
+
+            for field in Punctuated::<MetaNameValue, Token![,]>::parse_terminated(&content)? {
+                let value = match &field.lit {
+                    Lit::Str(value) => value.value(),
+                    lit => return Err(Error::new_spanned(lit, "expected a string literal")),
+                };
+
+                if field.path.is_ident("label") {
+                    adjective.label = Some(value);
+                } else if field.path.is_ident("description") {
+                    adjective.description = Some(value);
+                } else if field.path.is_ident("emoji") {
+                    adjective.emoji = Some(value);
+                } else {
+                    return Err(Error::new_spanned(
+                        field.path,
+                        "unknown key, expected `label`, `description` or `emoji`",
+                    ));
+                }
+            }
+        }
+
+        Ok(adjective)
+    }
+}
+
+/// The comma separated adjective list passed to `#[adjectives(...)]`, optionally
+/// followed by `retired(...)` naming adjectives that were removed from the list and
+/// `legacy(...)` freezing the list order data was encoded against before adjectives
+/// were stored by name.
+pub struct Adjectives {
+    pub adjectives: Vec<Adjective>,
+    pub retired: Vec<Ident>,
+    pub legacy: Vec<Ident>,
+}
+
+enum Entry {
+    Adjective(Adjective),
+    Retired(Punctuated<Ident, Token![,]>),
+    Legacy(Punctuated<Ident, Token![,]>),
+}
+
+impl Parse for Entry {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(syn::Ident) && input.peek2(token::Paren) {
+            let ident: Ident = input.parse()?;
+            let content;
+            parenthesized!(content in input);
+            let names = Punctuated::parse_terminated(&content)?;
+            return if ident == "retired" {
+                Ok(Entry::Retired(names))
+            } else if ident == "legacy" {
+                Ok(Entry::Legacy(names))
+            } else {
+                Err(Error::new(
+                    ident.span(),
+                    "expected `retired(...)` or `legacy(...)`",
+                ))
+            };
+        }
+
+        Ok(Entry::Adjective(input.parse()?))
+    }
+}
+
+impl Parse for Adjectives {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut adjectives = Self {
+            adjectives: Vec::new(),
+            retired: Vec::new(),
+            legacy: Vec::new(),
+        };
+
+        for entry in Punctuated::<Entry, Token![,]>::parse_terminated(input)? {
+            match entry {
+                Entry::Adjective(adjective) => adjectives.adjectives.push(adjective),
+                Entry::Retired(retired) => adjectives.retired.extend(retired),
+                Entry::Legacy(legacy) => adjectives.legacy.extend(legacy),
+            }
+        }
+
+        adjectives.validate(input.span())?;
+        Ok(adjectives)
+    }
+}
+
+impl Adjectives {
+    /// Rejects lists the generated code would mishandle, reporting every offending adjective.
+    ///
+    /// Adjectives are stored by name, so a retired name can never be reused: existing data
+    /// would silently start referring to the new adjective. Likewise every legacy name
+    /// must stay listed or retired.
+    fn validate(&self, span: Span) -> Result<()> {
+        if self.adjectives.is_empty() {
+            return Err(Error::new(span, "expected at least one adjective"));
+        }
+
+        let mut errors = Vec::new();
+        let mut seen = HashSet::new();
+
+        for adjective in &self.adjectives {
+            let name = adjective.ident.to_string();
+
+            if name
+                .chars()
+                .any(|c| !matches!(c, 'a'..='z' | '0'..='9' | '_'))
+            {
+                errors.push(Error::new(
+                    adjective.ident.span(),
+                    format!("invalid adjective `{name}`, expected lowercase ascii letters, digits and `_`"),
+                ));
+            }
+            if !seen.insert(adjective.name()) {
+                errors.push(Error::new(
+                    adjective.ident.span(),
+                    format!("duplicate adjective `{name}`"),
+                ));
+            }
+            if adjective.label().chars().count() > MAX_LENGTH
+                || adjective
+                    .description
+                    .iter()
+                    .any(|description| description.chars().count() > MAX_LENGTH)
+            {
+                errors.push(Error::new(
+                    adjective.ident.span(),
+                    format!("labels and descriptions of `{name}` must be at most {MAX_LENGTH} characters"),
+                ));
+            }
+        }
+
+        let mut retired = HashSet::new();
+        for ident in &self.retired {
+            let name = ident.to_string().replace('_', "-");
+            if seen.contains(&name) {
+                errors.push(Error::new(
+                    ident.span(),
+                    format!("adjective `{ident}` is retired and cannot be reused"),
+                ));
+            } else if !retired.insert(name) {
+                errors.push(Error::new(
+                    ident.span(),
+                    format!("duplicate retired adjective `{ident}`"),
+                ));
+            }
+        }
+
+        // Legacy data is decoded by name, which must still be known to be kept or dropped.
+        if self.legacy.len() > 64 {
+            errors.push(Error::new(span, "the legacy list holds at most 64 adjectives"));
+        }
+        for ident in &self.legacy {
+            let name = ident.to_string().replace('_', "-");
+            if !seen.contains(&name) && !retired.contains(&name) {
+                errors.push(Error::new(
+                    ident.span(),
+                    format!("legacy adjective `{ident}` must be listed or retired"),
+                ));
+            }
+        }
+
+        match errors.into_iter().reduce(|mut combined, error| {
+            combined.combine(error);
+            combined
+        }) {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
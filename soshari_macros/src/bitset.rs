@@ -0,0 +1,201 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::Visibility;
+
+/// Emits the flag type for `flags`, backed by the smallest integer that fits
+/// and falling back to a `[u64; N]` bitset past 128 adjectives.
+pub fn expand(vis: &Visibility, ident: &Ident, flags: &[Ident]) -> TokenStream {
+    match flags.len() {
+        0..=64 => bitflags(vis, ident, flags, quote!(u64)),
+        65..=128 => bitflags(vis, ident, flags, quote!(u128)),
+        _ => bitset(vis, ident, flags),
+    }
+}
+
+fn bitflags(vis: &Visibility, ident: &Ident, flags: &[Ident], bits: TokenStream) -> TokenStream {
+    let bit_offset = 0..flags.len();
+
+    quote! {
+        bitflags::bitflags! {
+            #[derive(Default)]
+            #vis struct #ident: #bits {
+                #(const #flags = 1 << #bit_offset;)*
+            }
+        }
+
+        impl #ident {
+            /// The number of adjectives set.
+            pub fn count(&self) -> u32 {
+                self.bits().count_ones()
+            }
+        }
+    }
+}
+
+/// A fixed-size bitset mirroring the subset of the `bitflags` API the generated code relies on.
+fn bitset(vis: &Visibility, ident: &Ident, flags: &[Ident]) -> TokenStream {
+    let len = flags.len();
+    let words = len.div_ceil(64);
+    let bit_offset = 0..len;
+
+    quote! {
+        #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+        #vis struct #ident([u64; #words]);
+
+        #[allow(dead_code)]
+        impl #ident {
+            #(const #flags: Self = Self::bit(#bit_offset);)*
+
+            const fn bit(offset: usize) -> Self {
+                let mut words = [0; #words];
+                words[offset / 64] = 1 << (offset % 64);
+                Self(words)
+            }
+
+            pub const fn empty() -> Self {
+                Self([0; #words])
+            }
+
+            pub const fn all() -> Self {
+                let mut words = [0; #words];
+                let mut offset = 0;
+                while offset < #len {
+                    words[offset / 64] |= 1 << (offset % 64);
+                    offset += 1;
+                }
+                Self(words)
+            }
+
+            pub fn is_empty(&self) -> bool {
+                *self == Self::empty()
+            }
+
+            pub fn is_all(&self) -> bool {
+                *self == Self::all()
+            }
+
+            pub fn contains(&self, other: Self) -> bool {
+                (*self & other) == other
+            }
+
+            pub fn intersects(&self, other: Self) -> bool {
+                !(*self & other).is_empty()
+            }
+
+            pub fn insert(&mut self, other: Self) {
+                *self |= other;
+            }
+
+            pub fn remove(&mut self, other: Self) {
+                *self &= !other;
+            }
+
+            pub fn toggle(&mut self, other: Self) {
+                *self ^= other;
+            }
+
+            pub fn set(&mut self, other: Self, value: bool) {
+                if value {
+                    self.insert(other);
+                } else {
+                    self.remove(other);
+                }
+            }
+
+            pub fn complement(self) -> Self {
+                !self
+            }
+
+            /// The number of adjectives set.
+            pub fn count(&self) -> u32 {
+                self.0.iter().map(|word| word.count_ones()).sum()
+            }
+        }
+
+        impl std::ops::BitOr for #ident {
+            type Output = Self;
+
+            fn bitor(mut self, other: Self) -> Self {
+                self |= other;
+                self
+            }
+        }
+
+        impl std::ops::BitOrAssign for #ident {
+            fn bitor_assign(&mut self, other: Self) {
+                for (word, other) in self.0.iter_mut().zip(other.0) {
+                    *word |= other;
+                }
+            }
+        }
+
+        impl std::ops::BitAnd for #ident {
+            type Output = Self;
+
+            fn bitand(mut self, other: Self) -> Self {
+                self &= other;
+                self
+            }
+        }
+
+        impl std::ops::BitAndAssign for #ident {
+            fn bitand_assign(&mut self, other: Self) {
+                for (word, other) in self.0.iter_mut().zip(other.0) {
+                    *word &= other;
+                }
+            }
+        }
+
+        impl std::ops::BitXor for #ident {
+            type Output = Self;
+
+            fn bitxor(mut self, other: Self) -> Self {
+                self ^= other;
+                self
+            }
+        }
+
+        impl std::ops::BitXorAssign for #ident {
+            fn bitxor_assign(&mut self, other: Self) {
+                for (word, other) in self.0.iter_mut().zip(other.0) {
+                    *word ^= other;
+                }
+            }
+        }
+
+        impl std::ops::Sub for #ident {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                self & !other
+            }
+        }
+
+        impl std::ops::SubAssign for #ident {
+            fn sub_assign(&mut self, other: Self) {
+                *self &= !other;
+            }
+        }
+
+        impl std::ops::Not for #ident {
+            type Output = Self;
+
+            fn not(mut self) -> Self {
+                for word in self.0.iter_mut() {
+                    *word = !*word;
+                }
+                self & Self::all()
+            }
+        }
+
+        impl std::fmt::Debug for #ident {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let mut set = f.debug_set();
+                #(if self.contains(Self::#flags) {
+                    set.entry(&stringify!(#flags));
+                })*
+                set.finish()
+            }
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::Visibility;
+
+/// Steps upgrading the stored JSON from version `n` to `n + 1`, indexed by `n`.
+///
+/// Version 0 is the bare array of windows written before the file was versioned.
+fn migrations() -> Vec<TokenStream> {
+    vec![quote! {
+        |windows| serde_json::json!({ "version": 1, "windows": windows })
+    }]
+}
+
+/// Emits the `{Ident}Group` collection persisting windows to a versioned JSON file.
+pub fn expand(vis: &Visibility, ident: &Ident, group_ident: &Ident) -> TokenStream {
+    let migrations = migrations();
+    let version = migrations.len() as u64;
+
+    quote! {
+        #vis struct #group_ident(Vec<#ident>);
+        impl #group_ident {
+            const VERSION: u64 = #version;
+
+            /// Loads the group, migrating older file versions. A file that cannot be
+            /// parsed is copied to `<path>.bak` and reported instead of being discarded.
+            fn load<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+                let path = path.as_ref();
+                let contents = std::fs::read_to_string(path)?;
+
+                Self::parse(&contents).map_err(|error| {
+                    let mut backup = path.as_os_str().to_owned();
+                    backup.push(".bak");
+                    let backup = std::path::PathBuf::from(backup);
+
+                    match std::fs::copy(path, &backup) {
+                        Ok(_) => std::io::Error::new(
+                            std::io::ErrorKind::InvalidData,
+                            format!(
+                                "cannot parse {} ({error}), a backup was written to {}",
+                                path.display(),
+                                backup.display(),
+                            ),
+                        ),
+                        Err(backup_error) => backup_error,
+                    }
+                })
+            }
+
+            fn parse(contents: &str) -> serde_json::Result<Self> {
+                const MIGRATIONS: &[fn(serde_json::Value) -> serde_json::Value] =
+                    &[#(#migrations),*];
+
+                if contents.trim().is_empty() {
+                    return Ok(Self(Vec::new()));
+                }
+
+                let mut value: serde_json::Value = serde_json::from_str(contents)?;
+                let mut version = if value.is_array() {
+                    0
+                } else {
+                    value["version"].as_u64().ok_or_else(|| {
+                        <serde_json::Error as serde::de::Error>::custom("missing version")
+                    })?
+                };
+
+                if version > Self::VERSION {
+                    return Err(<serde_json::Error as serde::de::Error>::custom(format!(
+                        "version {version} is newer than the supported version {}",
+                        Self::VERSION,
+                    )));
+                }
+
+                while version < Self::VERSION {
+                    value = MIGRATIONS[version as usize](value);
+                    version += 1;
+                }
+
+                Ok(Self(serde_json::from_value(value["windows"].take())?))
+            }
+
+            /// Writes the group to a temporary file renamed over `path`, so a crash
+            /// mid-write leaves the previous contents intact.
+            fn dump<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+                let path = path.as_ref();
+                let mut temporary = path.as_os_str().to_owned();
+                temporary.push(".tmp");
+
+                let file = std::fs::File::create(&temporary)?;
+                serde_json::to_writer_pretty(
+                    &file,
+                    &serde_json::json!({ "version": Self::VERSION, "windows": &self.0 }),
+                )?;
+                file.sync_all()?;
+                std::fs::rename(&temporary, path)
+            }
+
+            fn get<U: Into<u64> + Copy>(&self, id: U) -> Option<&#ident> {
+                self.0.iter().find(|n| n.id == id.into())
+            }
+
+            fn get_mut<U: Into<u64> + Copy>(&mut self, id: U) -> Option<&mut #ident> {
+                self.0.iter_mut().find(|n| n.id == id.into())
+            }
+
+            fn remove<U: Into<u64> + Copy>(&mut self, id: U) -> Option<#ident> {
+                let index = self.0.iter().position(|n| n.id == id.into())?;
+                Some(self.0.remove(index))
+            }
+
+            fn push(&mut self, other: #ident) {
+                match self.get_mut(other.id) {
+                    Some(s) => s.adjectives = other.adjectives,
+                    None => self.0.push(other),
+                }
+            }
+        }
+    }
+}
@@ -2,7 +2,14 @@ use proc_macro::TokenStream;
 
 use proc_macro2::{Ident, Span, TokenTree};
 use quote::quote;
-use syn::{parse_macro_input, DeriveInput};
+use syn::{parse_macro_input, DeriveInput, Item};
+
+mod adjective;
+mod bitset;
+mod group;
+mod slash_command;
+
+use adjective::{Adjective, Adjectives};
 
 #[proc_macro]
 pub fn generate_adjectives(stream: TokenStream) -> TokenStream {
@@ -68,75 +75,120 @@ pub fn generate_adjectives(stream: TokenStream) -> TokenStream {
 
 #[proc_macro_attribute]
 pub fn adjectives(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let attr = parse_macro_input!(attr as proc_macro2::TokenStream);
-
-    let adjectives = attr
-        .into_iter()
-        .filter_map(|tree| {
-            if let TokenTree::Ident(ident) = tree {
-                Some(ident.to_string().replace('_', "-"))
-            } else {
-                None
-            }
-        })
+    let Adjectives {
+        adjectives: attr,
+        legacy,
+        ..
+    } = parse_macro_input!(attr as Adjectives);
+    let legacy = legacy
+        .iter()
+        .map(|ident| ident.to_string().replace('_', "-"))
         .collect::<Vec<_>>();
 
-    let adjectives_upper_ident = adjectives
+    let adjectives = attr.iter().map(Adjective::name).collect::<Vec<_>>();
+    let adjectives_upper_ident = attr.iter().map(Adjective::upper_ident).collect::<Vec<_>>();
+    let labels = attr.iter().map(Adjective::label).collect::<Vec<_>>();
+    let optional = |value: &Option<String>| match value {
+        Some(value) => quote!(Some(#value)),
+        None => quote!(None),
+    };
+    let descriptions = attr
         .iter()
-        .map(|adjective| {
-            Ident::new(
-                &adjective.replace('-', "_").to_uppercase(),
-                Span::call_site(),
-            )
-        })
+        .map(|adjective| optional(&adjective.description))
+        .collect::<Vec<_>>();
+    let emojis = attr
+        .iter()
+        .map(|adjective| optional(&adjective.emoji))
         .collect::<Vec<_>>();
 
-    let derive = parse_macro_input!(item as DeriveInput);
-    let ident = &derive.ident;
+    // Either a window struct or an alias of a generic one, the generated types taking its
+    // visibility.
+    let item = parse_macro_input!(item as Item);
+    let (ident, vis) = match &item {
+        Item::Struct(item) => (&item.ident, &item.vis),
+        Item::Type(item) => (&item.ident, &item.vis),
+        item => {
+            return syn::Error::new_spanned(item, "expected a struct or a type alias")
+                .into_compile_error()
+                .into()
+        }
+    };
     let group_ident = Ident::new(&format!("{ident}Group"), Span::call_site());
 
     let bitmap_struct = Ident::new(&format!("{ident}Adjectives"), Span::call_site());
-    let bit_offset = 0..adjectives.len();
+    let bitmap = bitset::expand(vis, &bitmap_struct, &adjectives_upper_ident);
+    let group = group::expand(vis, ident, &group_ident);
 
     quote! {
-        #derive
-
-        struct #group_ident(Vec<#ident>);
-        impl #group_ident {
-            fn load<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
-                Ok(Self(
-                    serde_json::from_str(&std::fs::read_to_string(path)?).unwrap_or(Vec::new()),
-                ))
-            }
+        #item
 
-            fn dump<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
-                serde_json::to_writer_pretty(
-                    std::fs::OpenOptions::new().write(true).truncate(true).open(path)?,
-                    &self.0,
-                )?;
-                Ok(())
-            }
+        #group
 
-            fn get<U: Into<u64> + Copy>(&self, id: U) -> Option<&#ident> {
-                self.0.iter().find(|n| n.id == id.into())
-            }
+        #bitmap
 
-            fn get_mut<U: Into<u64> + Copy>(&mut self, id: U) -> Option<&mut #ident> {
-                self.0.iter_mut().find(|n| n.id == id.into())
+        // Adjectives are stored by name so that editing the list never reinterprets
+        // existing data; unknown and retired names are dropped on load.
+        impl serde::Serialize for #bitmap_struct {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_seq(self.as_adjectives())
             }
+        }
+
+        impl<'de> serde::Deserialize<'de> for #bitmap_struct {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct Visitor;
+
+                impl<'de> serde::de::Visitor<'de> for Visitor {
+                    type Value = #bitmap_struct;
+
+                    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        f.write_str("a list of adjectives")
+                    }
+
+                    fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                        self,
+                        mut seq: A,
+                    ) -> Result<Self::Value, A::Error> {
+                        let mut adjectives = Vec::new();
+                        while let Some(adjective) = seq.next_element::<String>()? {
+                            adjectives.push(adjective);
+                        }
+                        Ok(adjectives.into())
+                    }
+
+                    // Data written before adjectives were stored by name is `{ "bits": n }`,
+                    // with a bit per position of the frozen `legacy(...)` list. Without one
+                    // there is no order the bits could be read against.
+                    fn visit_map<A: serde::de::MapAccess<'de>>(
+                        self,
+                        mut map: A,
+                    ) -> Result<Self::Value, A::Error> {
+                        const LEGACY: &[&str] = &[#(#legacy),*];
 
-            fn push(&mut self, other: #ident) {
-                match self.get_mut(other.id) {
-                    Some(s) => s.adjectives = other.adjectives,
-                    None => self.0.push(other),
+                        let mut bits = None;
+                        while let Some((key, value)) = map.next_entry::<String, u64>()? {
+                            if key == "bits" {
+                                bits = Some(value);
+                            }
+                        }
+                        let bits = bits.ok_or_else(|| <A::Error as serde::de::Error>::missing_field("bits"))?;
+                        if LEGACY.is_empty() {
+                            return Err(<A::Error as serde::de::Error>::custom(
+                                "bit encoded adjectives need a legacy(...) list to be decoded",
+                            ));
+                        }
+
+                        Ok(LEGACY
+                            .iter()
+                            .enumerate()
+                            .filter(|(offset, _)| bits & (1 << offset) != 0)
+                            .map(|(_, adjective)| adjective.to_string())
+                            .collect::<Vec<_>>()
+                            .into())
+                    }
                 }
-            }
-        }
 
-        bitflags::bitflags! {
-            #[derive(serde::Serialize, serde::Deserialize, Default)]
-            struct #bitmap_struct: u64 {
-                #(const #adjectives_upper_ident = 1 << #bit_offset;)*
+                deserializer.deserialize_any(Visitor)
             }
         }
 
@@ -164,11 +216,59 @@ pub fn adjectives(attr: TokenStream, item: TokenStream) -> TokenStream {
                 adjective_vec
             }
 
+            pub fn as_labels(&self) -> Vec<&'static str> {
+                let mut label_vec = Vec::new();
+
+                #(if self.contains(Self::#adjectives_upper_ident) {
+                    label_vec.push(#labels);
+                })*
+
+                label_vec
+            }
+
             #[inline]
             pub fn adjectives() -> &'static [&'static str] {
                 &[#(#adjectives),*]
             }
+
+            /// The name shown to users for `adjective`.
+            pub fn label(adjective: &str) -> Option<&'static str> {
+                match adjective {
+                    #(#adjectives => Some(#labels),)*
+                    _ => None,
+                }
+            }
+
+            pub fn description(adjective: &str) -> Option<&'static str> {
+                match adjective {
+                    #(#adjectives => #descriptions,)*
+                    _ => None,
+                }
+            }
+
+            pub fn emoji(adjective: &str) -> Option<&'static str> {
+                match adjective {
+                    #(#adjectives => #emojis,)*
+                    _ => None,
+                }
+            }
+
+            /// Jaccard similarity with `other`: the adjectives both contain over those
+            /// either contains, `None` when both are empty.
+            pub fn similarity(&self, other: &Self) -> Option<f64> {
+                let either = (*self | *other).count();
+                (either > 0).then(|| (*self & *other).count() as f64 / either as f64)
+            }
         }
     }
     .into()
 }
+
+#[proc_macro_derive(SlashCommand, attributes(command, option, localized))]
+pub fn slash_command(item: TokenStream) -> TokenStream {
+    let derive = parse_macro_input!(item as DeriveInput);
+
+    slash_command::expand(derive)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,385 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    Attribute, Data, DeriveInput, Error, Fields, GenericArgument, Lit, Meta, NestedMeta,
+    PathArguments, Result, Type,
+};
+
+#[derive(Default)]
+struct Metadata {
+    name: Option<String>,
+    description: Option<String>,
+    /// Only read from `#[option(...)]`.
+    autocomplete: bool,
+}
+
+/// A translation of a command, subcommand or option for a Discord locale.
+struct Localization {
+    locale: String,
+    name: Option<String>,
+    description: Option<String>,
+}
+
+/// Reads `name = "..."` and `description = "..."` out of every `#[<path>(...)]` attribute,
+/// as well as `autocomplete = true` for options.
+fn metadata(attrs: &[Attribute], path: &str) -> Result<Metadata> {
+    let mut metadata = Metadata::default();
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident(path)) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => {
+                return Err(Error::new_spanned(
+                    meta,
+                    format!("expected #[{path}(name = \"...\", description = \"...\")]"),
+                ))
+            }
+        };
+
+        for nested in list.nested {
+            let name_value = match nested {
+                NestedMeta::Meta(Meta::NameValue(name_value)) => name_value,
+                nested => return Err(Error::new_spanned(nested, "expected `key = \"value\"`")),
+            };
+            if path == "option" && name_value.path.is_ident("autocomplete") {
+                metadata.autocomplete = match &name_value.lit {
+                    Lit::Bool(value) => value.value,
+                    lit => return Err(Error::new_spanned(lit, "expected `true` or `false`")),
+                };
+                continue;
+            }
+            let value = match &name_value.lit {
+                Lit::Str(value) => value.value(),
+                lit => return Err(Error::new_spanned(lit, "expected a string literal")),
+            };
+
+            if name_value.path.is_ident("name") {
+                metadata.name = Some(value);
+            } else if name_value.path.is_ident("description") {
+                metadata.description = Some(value);
+            } else {
+                return Err(Error::new_spanned(
+                    name_value.path,
+                    "unknown key, expected `name` or `description`",
+                ));
+            }
+        }
+    }
+
+    Ok(metadata)
+}
+
+/// Reads every `#[localized(locale = "...", name = "...", description = "...")]` attribute.
+fn localizations(attrs: &[Attribute]) -> Result<Vec<Localization>> {
+    let mut localizations = Vec::new();
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("localized")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => return Err(Error::new_spanned(
+                meta,
+                "expected #[localized(locale = \"...\", name = \"...\", description = \"...\")]",
+            )),
+        };
+
+        let mut locale = None;
+        let mut localization = Metadata::default();
+        for nested in list.nested {
+            let name_value = match nested {
+                NestedMeta::Meta(Meta::NameValue(name_value)) => name_value,
+                nested => return Err(Error::new_spanned(nested, "expected `key = \"value\"`")),
+            };
+            let value = match &name_value.lit {
+                Lit::Str(value) => value.value(),
+                lit => return Err(Error::new_spanned(lit, "expected a string literal")),
+            };
+
+            if name_value.path.is_ident("locale") {
+                locale = Some(value);
+            } else if name_value.path.is_ident("name") {
+                localization.name = Some(value);
+            } else if name_value.path.is_ident("description") {
+                localization.description = Some(value);
+            } else {
+                return Err(Error::new_spanned(
+                    name_value.path,
+                    "unknown key, expected `locale`, `name` or `description`",
+                ));
+            }
+        }
+
+        localizations.push(Localization {
+            locale: locale.ok_or_else(|| Error::new_spanned(attr, "missing `locale = \"...\"`"))?,
+            name: localization.name,
+            description: localization.description,
+        });
+    }
+
+    Ok(localizations)
+}
+
+/// Chains the `name_localized` and `description_localized` calls of `localizations` onto
+/// a command or option builder.
+fn localize(localizations: &[Localization]) -> TokenStream {
+    localizations
+        .iter()
+        .map(|localization| {
+            let locale = &localization.locale;
+            let name = localization
+                .name
+                .as_ref()
+                .map(|name| quote!(.name_localized(#locale, #name)));
+            let description = localization
+                .description
+                .as_ref()
+                .map(|description| quote!(.description_localized(#locale, #description)));
+            quote!(#name #description)
+        })
+        .collect()
+}
+
+fn snake_case(ident: &str) -> String {
+    let mut name = String::new();
+    for (i, c) in ident.chars().enumerate() {
+        if c.is_uppercase() && i != 0 {
+            name.push('_');
+        }
+        name.extend(c.to_lowercase());
+    }
+    name
+}
+
+/// Returns `T` if `ty` is `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(path) => path.path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => match arguments.args.first()? {
+            GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Maps a field type onto its `CommandOptionType` and an expression reading it out of `value`.
+fn option_kind(ty: &Type) -> Option<(TokenStream, TokenStream)> {
+    let segment = match ty {
+        Type::Path(path) => path.path.segments.last()?,
+        _ => return None,
+    };
+    let kind = match segment.ident.to_string().as_str() {
+        "UserId" => (quote!(User), quote!(value.as_user_id())),
+        "RoleId" => (quote!(Role), quote!(value.as_role_id())),
+        "ChannelId" => (quote!(Channel), quote!(value.as_channel_id())),
+        "AttachmentId" => (quote!(Attachment), quote!(value.as_attachment_id())),
+        "String" => (
+            quote!(String),
+            quote!(value.as_str().map(ToString::to_string)),
+        ),
+        "i64" => (quote!(Integer), quote!(value.as_i64())),
+        "f64" => (quote!(Number), quote!(value.as_f64())),
+        "bool" => (quote!(Boolean), quote!(value.as_bool())),
+        _ => return None,
+    };
+    Some(kind)
+}
+
+pub fn expand(derive: DeriveInput) -> Result<TokenStream> {
+    let ident = &derive.ident;
+    let data = match &derive.data {
+        Data::Enum(data) => data,
+        _ => {
+            return Err(Error::new_spanned(
+                ident,
+                "SlashCommand can only be derived for an enum of subcommands",
+            ))
+        }
+    };
+
+    let command = metadata(&derive.attrs, "command")?;
+    let command_name = command
+        .name
+        .unwrap_or_else(|| snake_case(&ident.to_string()));
+    let command_description = command
+        .description
+        .ok_or_else(|| Error::new_spanned(ident, "missing #[command(description = \"...\")]"))?;
+    let command_localizations = localize(&localizations(&derive.attrs)?);
+
+    let mut subcommands = Vec::new();
+    let mut parsers = Vec::new();
+
+    for variant in &data.variants {
+        let variant_ident = &variant.ident;
+        let subcommand = metadata(&variant.attrs, "command")?;
+        let subcommand_name = subcommand
+            .name
+            .unwrap_or_else(|| snake_case(&variant_ident.to_string()));
+        let subcommand_description = subcommand.description.ok_or_else(|| {
+            Error::new_spanned(variant_ident, "missing #[command(description = \"...\")]")
+        })?;
+        let subcommand_localizations = localize(&localizations(&variant.attrs)?);
+
+        let fields = match &variant.fields {
+            Fields::Named(fields) => fields.named.iter().collect(),
+            Fields::Unit => Vec::new(),
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                // A single unnamed field is a nested `SlashCommand` enum forming a subcommand group.
+                let group = &fields.unnamed[0].ty;
+                subcommands.push(quote! {
+                    <#group>::options().into_iter().fold(
+                        serenity::builder::CreateCommandOption::new(
+                            serenity::model::prelude::command::CommandOptionType::SubCommandGroup,
+                            #subcommand_name,
+                            #subcommand_description,
+                        )
+                        #subcommand_localizations,
+                        |group, option| group.add_sub_option(option),
+                    )
+                });
+                parsers.push(quote! {
+                    (
+                        #subcommand_name,
+                        serenity::model::prelude::CommandDataOptionValue::SubCommandGroup(options),
+                    ) => Some(Self::#variant_ident(<#group>::parse_options(options)?))
+                });
+                continue;
+            }
+            Fields::Unnamed(fields) => {
+                return Err(Error::new_spanned(
+                    fields,
+                    "subcommand options must be named fields, or a single subcommand group",
+                ))
+            }
+        };
+
+        let mut sub_options = Vec::new();
+        let mut field_parsers = Vec::new();
+
+        for field in fields {
+            let field_ident = field.ident.as_ref().unwrap();
+            let option = metadata(&field.attrs, "option")?;
+            let option_name = option.name.unwrap_or_else(|| field_ident.to_string());
+            let option_description = option.description.ok_or_else(|| {
+                Error::new_spanned(field_ident, "missing #[option(description = \"...\")]")
+            })?;
+            let option_localizations = localize(&localizations(&field.attrs)?);
+
+            let inner = option_inner(&field.ty);
+            let required = inner.is_none();
+            let (kind, extract) = option_kind(inner.unwrap_or(&field.ty)).ok_or_else(|| {
+                Error::new_spanned(
+                    &field.ty,
+                    "unsupported option type, expected one of UserId, RoleId, ChannelId, AttachmentId, String, i64, f64 or bool",
+                )
+            })?;
+            // Discord only autocompletes free-form options.
+            let autocomplete = if !option.autocomplete {
+                None
+            } else if matches!(kind.to_string().as_str(), "String" | "Integer" | "Number") {
+                Some(quote!(.set_autocomplete(true)))
+            } else {
+                return Err(Error::new_spanned(
+                    &field.ty,
+                    "autocomplete requires a String, i64 or f64 option",
+                ));
+            };
+
+            sub_options.push(quote! {
+                .add_sub_option(
+                    serenity::builder::CreateCommandOption::new(
+                        serenity::model::prelude::command::CommandOptionType::#kind,
+                        #option_name,
+                        #option_description,
+                    )
+                    .required(#required)
+                    #autocomplete
+                    #option_localizations,
+                )
+            });
+
+            let value = quote! {
+                options
+                    .iter()
+                    .find(|option| option.name == #option_name)
+                    .and_then(|option| {
+                        let value = &option.value;
+                        #extract
+                    })
+            };
+            field_parsers.push(if required {
+                quote!(#field_ident: #value?)
+            } else {
+                quote!(#field_ident: #value)
+            });
+        }
+
+        subcommands.push(quote! {
+            serenity::builder::CreateCommandOption::new(
+                serenity::model::prelude::command::CommandOptionType::SubCommand,
+                #subcommand_name,
+                #subcommand_description,
+            )
+            #subcommand_localizations
+            #(#sub_options)*
+        });
+
+        parsers.push(match &variant.fields {
+            Fields::Unit => quote! {
+                (
+                    #subcommand_name,
+                    serenity::model::prelude::CommandDataOptionValue::SubCommand(_),
+                ) => Some(Self::#variant_ident)
+            },
+            _ => quote! {
+                (
+                    #subcommand_name,
+                    serenity::model::prelude::CommandDataOptionValue::SubCommand(options),
+                ) => Some(Self::#variant_ident { #(#field_parsers),* })
+            },
+        });
+    }
+
+    Ok(quote! {
+        impl #ident {
+            /// Builds the application command registered for this enum.
+            pub fn create() -> serenity::builder::CreateCommand {
+                Self::options().into_iter().fold(
+                    serenity::builder::CreateCommand::new(#command_name)
+                        .description(#command_description)
+                        #command_localizations,
+                    |command, option| command.add_option(option),
+                )
+            }
+
+            /// The subcommands of this enum, also used when it is nested as a subcommand group.
+            pub fn options() -> Vec<serenity::builder::CreateCommandOption> {
+                vec![#(#subcommands),*]
+            }
+
+            /// Parses the invoked subcommand and its options, returning `None` if the
+            /// interaction does not match this command.
+            pub fn parse(command: &serenity::model::prelude::CommandInteraction) -> Option<Self> {
+                if command.data.name != #command_name {
+                    return None;
+                }
+
+                Self::parse_options(&command.data.options)
+            }
+
+            pub fn parse_options(options: &[serenity::model::prelude::CommandDataOption]) -> Option<Self> {
+                let subcommand = options.first()?;
+
+                match (subcommand.name.as_str(), &subcommand.value) {
+                    #(#parsers,)*
+                    _ => None,
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,120 @@
+use serde_json::json;
+use serenity::model::prelude::{CommandDataOption, RoleId, UserId};
+use soshari_macros::SlashCommand;
+
+#[derive(SlashCommand, Debug, PartialEq)]
+#[command(name = "foo", description = "The foo command")]
+pub enum FooCommand {
+    #[command(description = "Start the foo test")]
+    Start {
+        #[option(description = "User to contribute to")]
+        user: Option<UserId>,
+    },
+    #[command(description = "Query for a user")]
+    Query {
+        #[option(description = "User to query")]
+        user: UserId,
+        #[option(name = "as", description = "Role to query as")]
+        role: Option<RoleId>,
+        #[option(description = "Number of entries")]
+        count: i64,
+    },
+    #[command(name = "list-all", description = "List everything")]
+    ListAll,
+}
+
+fn main() {
+    let command = serde_json::to_value(FooCommand::create()).unwrap();
+
+    assert_eq!(command["name"], "foo");
+    assert_eq!(command["description"], "The foo command");
+
+    let subcommands = command["options"].as_array().unwrap();
+    assert_eq!(subcommands.len(), 3);
+    assert_eq!(subcommands[0]["name"], "start");
+    assert_eq!(subcommands[0]["options"][0]["name"], "user");
+    assert_eq!(subcommands[0]["options"][0]["required"], false);
+    assert_eq!(subcommands[1]["options"][0]["required"], true);
+    assert_eq!(subcommands[1]["options"][1]["name"], "as");
+    assert_eq!(subcommands[2]["name"], "list-all");
+
+    assert_eq!(
+        FooCommand::parse_options(&command_options(
+            json!([{ "name": "start", "type": 1, "options": [] }])
+        )),
+        Some(FooCommand::Start { user: None })
+    );
+    assert_eq!(
+        FooCommand::parse_options(&command_options(
+            json!([{ "name": "start", "type": 1, "options": [{ "name": "user", "type": 6, "value": "7" }] }])
+        )),
+        Some(FooCommand::Start {
+            user: Some(UserId::new(7))
+        })
+    );
+    assert_eq!(
+        FooCommand::parse_options(&command_options(json!([{
+            "name": "query",
+            "type": 1,
+            "options": [
+                { "name": "user", "type": 6, "value": "7" },
+                { "name": "as", "type": 8, "value": "9" },
+                { "name": "count", "type": 4, "value": 3 }
+            ]
+        }]))),
+        Some(FooCommand::Query {
+            user: UserId::new(7),
+            role: Some(RoleId::new(9)),
+            count: 3
+        })
+    );
+    assert_eq!(
+        FooCommand::parse_options(&command_options(json!([{
+            "name": "query",
+            "type": 1,
+            "options": [{ "name": "user", "type": 6, "value": "7" }, { "name": "count", "type": 4, "value": 3 }]
+        }]))),
+        Some(FooCommand::Query {
+            user: UserId::new(7),
+            role: None,
+            count: 3
+        })
+    );
+    assert_eq!(
+        FooCommand::parse_options(&command_options(
+            json!([{ "name": "list-all", "type": 1, "options": [] }])
+        )),
+        Some(FooCommand::ListAll)
+    );
+
+    // A required option is missing
+    assert_eq!(
+        FooCommand::parse_options(&command_options(
+            json!([{ "name": "query", "type": 1, "options": [{ "name": "user", "type": 6, "value": "7" }] }])
+        )),
+        None
+    );
+    // An option of the wrong type
+    assert_eq!(
+        FooCommand::parse_options(&command_options(json!([{
+            "name": "query",
+            "type": 1,
+            "options": [{ "name": "user", "type": 3, "value": "7" }, { "name": "count", "type": 4, "value": 3 }]
+        }]))),
+        None
+    );
+    // An unknown subcommand
+    assert_eq!(
+        FooCommand::parse_options(&command_options(
+            json!([{ "name": "bar", "type": 1, "options": [] }])
+        )),
+        None
+    );
+
+    let _ = FooCommand::parse;
+}
+
+/// The options of a slash command interaction, as sent by Discord.
+fn command_options(options: serde_json::Value) -> Vec<CommandDataOption> {
+    serde_json::from_value(options).unwrap()
+}
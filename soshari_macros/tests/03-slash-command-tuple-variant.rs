@@ -0,0 +1,11 @@
+use serenity::model::prelude::UserId;
+use soshari_macros::SlashCommand;
+
+#[derive(SlashCommand)]
+#[command(name = "foo", description = "The foo command")]
+pub enum FooCommand {
+    #[command(description = "Query for a user")]
+    Query(UserId, UserId),
+}
+
+fn main() {}
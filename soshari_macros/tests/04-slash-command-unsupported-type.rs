@@ -0,0 +1,13 @@
+use soshari_macros::SlashCommand;
+
+#[derive(SlashCommand)]
+#[command(name = "foo", description = "The foo command")]
+pub enum FooCommand {
+    #[command(description = "Query for a user")]
+    Query {
+        #[option(description = "Users to query")]
+        users: Vec<u64>,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+use soshari_macros::adjectives;
+
+#[adjectives(
+    calm,
+    blase = { label = "blasé", description = "Unimpressed by everything", emoji = "😑" },
+    self_assertive = { emoji = "💪" },
+)]
+#[derive(Serialize, Deserialize)]
+pub struct Foo {
+    id: u64,
+    adjectives: FooAdjectives,
+    others: Vec<Foo>,
+}
+
+fn main() {
+    assert_eq!(
+        FooAdjectives::adjectives(),
+        &["calm", "blase", "self-assertive"]
+    );
+
+    assert_eq!(FooAdjectives::label("calm"), Some("calm"));
+    assert_eq!(FooAdjectives::label("blase"), Some("blasé"));
+    assert_eq!(
+        FooAdjectives::label("self-assertive"),
+        Some("self-assertive")
+    );
+    assert_eq!(FooAdjectives::label("unknown"), None);
+
+    assert_eq!(FooAdjectives::description("calm"), None);
+    assert_eq!(
+        FooAdjectives::description("blase"),
+        Some("Unimpressed by everything")
+    );
+    assert_eq!(FooAdjectives::emoji("self-assertive"), Some("💪"));
+
+    let flags = FooAdjectives::from(vec!["blase".to_string(), "calm".to_string()]);
+    assert_eq!(flags.as_adjectives(), vec!["calm", "blase"]);
+    assert_eq!(flags.as_labels(), vec!["calm", "blasé"]);
+}
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use soshari_macros::adjectives;
+
+#[adjectives(
+    trait_000, trait_001, trait_002, trait_003, trait_004, trait_005, trait_006, trait_007, trait_008, trait_009,
+    trait_010, trait_011, trait_012, trait_013, trait_014, trait_015, trait_016, trait_017, trait_018, trait_019,
+    trait_020, trait_021, trait_022, trait_023, trait_024, trait_025, trait_026, trait_027, trait_028, trait_029,
+    trait_030, trait_031, trait_032, trait_033, trait_034, trait_035, trait_036, trait_037, trait_038, trait_039,
+    trait_040, trait_041, trait_042, trait_043, trait_044, trait_045, trait_046, trait_047, trait_048, trait_049,
+    trait_050, trait_051, trait_052, trait_053, trait_054, trait_055, trait_056, trait_057, trait_058, trait_059,
+    trait_060, trait_061, trait_062, trait_063, trait_064, trait_065, trait_066, trait_067, trait_068, trait_069,
+    trait_070, trait_071, trait_072, trait_073, trait_074, trait_075, trait_076, trait_077, trait_078, trait_079,
+    trait_080, trait_081, trait_082, trait_083, trait_084, trait_085, trait_086, trait_087, trait_088, trait_089,
+    trait_090, trait_091, trait_092, trait_093, trait_094, trait_095, trait_096, trait_097, trait_098, trait_099,
+)]
+#[derive(Serialize, Deserialize)]
+pub struct Wide {
+    id: u64,
+    adjectives: WideAdjectives,
+    others: Vec<Wide>,
+}
+
+fn check_wide() {
+    let flags = WideAdjectives::TRAIT_000 | WideAdjectives::TRAIT_099;
+    assert_eq!(flags.bits(), 1 | 1 << 99);
+    assert_eq!(flags.as_adjectives(), vec!["trait-000", "trait-099"]);
+    assert_eq!(WideAdjectives::all().as_adjectives().len(), 100);
+}
+
+#[adjectives(
+    trait_000, trait_001, trait_002, trait_003, trait_004, trait_005, trait_006, trait_007, trait_008, trait_009,
+    trait_010, trait_011, trait_012, trait_013, trait_014, trait_015, trait_016, trait_017, trait_018, trait_019,
+    trait_020, trait_021, trait_022, trait_023, trait_024, trait_025, trait_026, trait_027, trait_028, trait_029,
+    trait_030, trait_031, trait_032, trait_033, trait_034, trait_035, trait_036, trait_037, trait_038, trait_039,
+    trait_040, trait_041, trait_042, trait_043, trait_044, trait_045, trait_046, trait_047, trait_048, trait_049,
+    trait_050, trait_051, trait_052, trait_053, trait_054, trait_055, trait_056, trait_057, trait_058, trait_059,
+    trait_060, trait_061, trait_062, trait_063, trait_064, trait_065, trait_066, trait_067, trait_068, trait_069,
+    trait_070, trait_071, trait_072, trait_073, trait_074, trait_075, trait_076, trait_077, trait_078, trait_079,
+    trait_080, trait_081, trait_082, trait_083, trait_084, trait_085, trait_086, trait_087, trait_088, trait_089,
+    trait_090, trait_091, trait_092, trait_093, trait_094, trait_095, trait_096, trait_097, trait_098, trait_099,
+    trait_100, trait_101, trait_102, trait_103, trait_104, trait_105, trait_106, trait_107, trait_108, trait_109,
+    trait_110, trait_111, trait_112, trait_113, trait_114, trait_115, trait_116, trait_117, trait_118, trait_119,
+    trait_120, trait_121, trait_122, trait_123, trait_124, trait_125, trait_126, trait_127, trait_128, trait_129,
+)]
+#[derive(Serialize, Deserialize)]
+pub struct Huge {
+    id: u64,
+    adjectives: HugeAdjectives,
+    others: Vec<Huge>,
+}
+
+fn main() {
+    check_wide();
+
+    let flags = HugeAdjectives::from(vec![
+        "trait-000".to_string(),
+        "trait-064".to_string(),
+        "trait-129".to_string(),
+    ]);
+    assert_eq!(flags.as_adjectives(), vec!["trait-000", "trait-064", "trait-129"]);
+    assert!(flags.contains(HugeAdjectives::TRAIT_064));
+    assert!(!flags.contains(HugeAdjectives::TRAIT_065));
+
+    let complement = flags.complement();
+    assert_eq!(complement.as_adjectives().len(), 127);
+    assert!((flags & complement).is_empty());
+    assert!((flags | complement).is_all());
+    assert_eq!(!HugeAdjectives::empty(), HugeAdjectives::all());
+
+    let mut other = HugeAdjectives::TRAIT_064 | HugeAdjectives::TRAIT_100;
+    other &= !flags;
+    assert_eq!(other, HugeAdjectives::TRAIT_100);
+
+    let json = serde_json::to_string(&flags).unwrap();
+    assert_eq!(serde_json::from_str::<HugeAdjectives>(&json).unwrap(), flags);
+}
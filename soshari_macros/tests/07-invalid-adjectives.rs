@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+use soshari_macros::adjectives;
+
+#[adjectives(calm, Bold, calm, witty = { label = "a label far too long to ever fit into a discord select menu option, which only allows a hundred characters" })]
+#[derive(Serialize, Deserialize)]
+pub struct Foo {
+    id: u64,
+    adjectives: FooAdjectives,
+    others: Vec<Foo>,
+}
+
+fn main() {}
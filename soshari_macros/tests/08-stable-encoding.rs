@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use soshari_macros::adjectives;
+
+mod before {
+    use super::*;
+
+    #[adjectives(calm, bold, shy, witty, legacy(calm, bold, shy, witty))]
+    #[derive(Serialize, Deserialize)]
+    pub struct Foo {
+        pub id: u64,
+        pub adjectives: FooAdjectives,
+        pub others: Vec<Foo>,
+    }
+
+    pub fn dump() -> String {
+        serde_json::to_string(&Foo {
+            id: 1,
+            adjectives: vec!["shy".to_string(), "witty".to_string()].into(),
+            others: Vec::new(),
+        })
+        .unwrap()
+    }
+}
+
+// `brave` was inserted at the front, `shy` removed.
+#[adjectives(brave, calm, bold, witty, retired(shy), legacy(calm, bold, shy, witty))]
+#[derive(Serialize, Deserialize)]
+pub struct Foo {
+    id: u64,
+    adjectives: FooAdjectives,
+    others: Vec<Foo>,
+}
+
+#[adjectives(calm, bold)]
+#[derive(Serialize, Deserialize)]
+pub struct Bar {
+    id: u64,
+    adjectives: BarAdjectives,
+    others: Vec<Bar>,
+}
+
+fn main() {
+    let json = before::dump();
+    assert_eq!(json, r#"{"id":1,"adjectives":["shy","witty"],"others":[]}"#);
+
+    let foo: Foo = serde_json::from_str(&json).unwrap();
+    assert_eq!(foo.id, 1);
+    assert!(foo.others.is_empty());
+    assert_eq!(foo.adjectives, FooAdjectives::WITTY);
+
+    let unknown: FooAdjectives = serde_json::from_str(r#"["bold", "grumpy"]"#).unwrap();
+    assert_eq!(unknown, FooAdjectives::BOLD);
+
+    // Bit per position of the legacy list, as written before adjectives were stored by
+    // name: inserting `brave` does not change what it decodes to.
+    let legacy = r#"{"bits":10}"#;
+    let before: before::FooAdjectives = serde_json::from_str(legacy).unwrap();
+    let after: FooAdjectives = serde_json::from_str(legacy).unwrap();
+    assert_eq!(before.as_adjectives(), vec!["bold", "witty"]);
+    assert_eq!(after.as_adjectives(), before.as_adjectives());
+
+    // Retired adjectives are dropped like stored names.
+    let retired: FooAdjectives = serde_json::from_str(r#"{"bits":5}"#).unwrap();
+    assert_eq!(retired, FooAdjectives::CALM);
+
+    // Without a legacy list there is no order to read bits against.
+    assert!(serde_json::from_str::<BarAdjectives>(r#"{"bits":1}"#).is_err());
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+use soshari_macros::adjectives;
+
+#[adjectives(calm, bold, shy, retired(shy, witty, witty), legacy(calm, witty, grumpy))]
+#[derive(Serialize, Deserialize)]
+pub struct Foo {
+    id: u64,
+    adjectives: FooAdjectives,
+    others: Vec<Foo>,
+}
+
+fn main() {}
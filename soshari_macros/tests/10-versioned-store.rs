@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+use soshari_macros::adjectives;
+
+#[adjectives(calm, bold, shy, witty, legacy(calm, bold, shy, witty))]
+#[derive(Serialize, Deserialize)]
+pub struct Foo {
+    id: u64,
+    adjectives: FooAdjectives,
+    others: Vec<Foo>,
+}
+
+fn fixture(name: &str, contents: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("soshari-store-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+fn assert_window(group: &FooGroup) {
+    let foo = group.get(1u64).unwrap();
+    assert_eq!(foo.adjectives, FooAdjectives::CALM | FooAdjectives::SHY);
+    assert_eq!(foo.others.len(), 1);
+    assert_eq!(foo.others[0].id, 2);
+    assert_eq!(foo.others[0].adjectives, FooAdjectives::BOLD);
+}
+
+fn main() {
+    let v0 = fixture("v0.json", include_str!("fixtures/v0.json"));
+    let group = FooGroup::load(&v0).unwrap();
+    assert_window(&group);
+
+    group.dump(&v0).unwrap();
+    let dumped: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&v0).unwrap()).unwrap();
+    let v1: serde_json::Value = serde_json::from_str(include_str!("fixtures/v1.json")).unwrap();
+    assert_eq!(dumped, v1);
+
+    let v1 = fixture("v1.json", include_str!("fixtures/v1.json"));
+    assert_window(&FooGroup::load(&v1).unwrap());
+
+    let empty = fixture("empty.json", "");
+    assert!(FooGroup::load(&empty).unwrap().0.is_empty());
+
+    let corrupt = fixture("corrupt.json", include_str!("fixtures/corrupt.json"));
+    let error = FooGroup::load(&corrupt).err().unwrap();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(
+        std::fs::read_to_string(corrupt.with_extension("json.bak")).unwrap(),
+        include_str!("fixtures/corrupt.json"),
+    );
+
+    let future = fixture("future.json", include_str!("fixtures/future.json"));
+    assert!(FooGroup::load(&future).is_err());
+}
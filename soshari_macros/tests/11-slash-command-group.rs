@@ -0,0 +1,91 @@
+use serde_json::json;
+use serenity::model::prelude::{CommandDataOption, UserId};
+use soshari_macros::SlashCommand;
+
+#[derive(SlashCommand, Debug, PartialEq)]
+#[command(name = "admin", description = "Administrate foo")]
+pub enum AdminCommand {
+    #[command(description = "Ban a user")]
+    Ban {
+        #[option(description = "User to ban")]
+        user: UserId,
+    },
+    #[command(description = "List the bans")]
+    Bans,
+}
+
+#[derive(SlashCommand, Debug, PartialEq)]
+#[command(name = "foo", description = "The foo command")]
+pub enum FooCommand {
+    #[command(description = "Start the foo test")]
+    Start,
+    #[command(description = "Administrate foo")]
+    Admin(AdminCommand),
+}
+
+fn main() {
+    let command = serde_json::to_value(FooCommand::create()).unwrap();
+
+    let options = command["options"].as_array().unwrap();
+    assert_eq!(options.len(), 2);
+    assert_eq!(options[1]["name"], "admin");
+    // SubCommandGroup
+    assert_eq!(options[1]["type"], 2);
+
+    let subcommands = options[1]["options"].as_array().unwrap();
+    assert_eq!(subcommands.len(), 2);
+    assert_eq!(subcommands[0]["name"], "ban");
+    // SubCommand
+    assert_eq!(subcommands[0]["type"], 1);
+    assert_eq!(subcommands[0]["options"][0]["name"], "user");
+    assert_eq!(subcommands[1]["name"], "bans");
+
+    assert_eq!(
+        FooCommand::parse_options(&command_options(
+            json!([{ "name": "start", "type": 1, "options": [] }])
+        )),
+        Some(FooCommand::Start)
+    );
+    assert_eq!(
+        FooCommand::parse_options(&command_options(json!([{
+            "name": "admin",
+            "type": 2,
+            "options": [{ "name": "ban", "type": 1, "options": [{ "name": "user", "type": 6, "value": "7" }] }]
+        }]))),
+        Some(FooCommand::Admin(AdminCommand::Ban {
+            user: UserId::new(7)
+        }))
+    );
+    assert_eq!(
+        FooCommand::parse_options(&command_options(json!([{
+            "name": "admin",
+            "type": 2,
+            "options": [{ "name": "bans", "type": 1, "options": [] }]
+        }]))),
+        Some(FooCommand::Admin(AdminCommand::Bans))
+    );
+
+    // The required user is missing
+    assert_eq!(
+        FooCommand::parse_options(&command_options(json!([{
+            "name": "admin",
+            "type": 2,
+            "options": [{ "name": "ban", "type": 1, "options": [] }]
+        }]))),
+        None
+    );
+    // A subcommand where a group is expected
+    assert_eq!(
+        FooCommand::parse_options(&command_options(
+            json!([{ "name": "admin", "type": 1, "options": [] }])
+        )),
+        None
+    );
+
+    let _ = FooCommand::parse;
+}
+
+/// The options of a slash command interaction, as sent by Discord.
+fn command_options(options: serde_json::Value) -> Vec<CommandDataOption> {
+    serde_json::from_value(options).unwrap()
+}
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use soshari_macros::adjectives;
+
+#[adjectives(bold, calm, shy, witty)]
+#[derive(Serialize, Deserialize)]
+pub struct Small {
+    id: u64,
+    adjectives: SmallAdjectives,
+    others: Vec<Small>,
+}
+
+#[adjectives(
+    trait_000, trait_001, trait_002, trait_003, trait_004, trait_005, trait_006, trait_007, trait_008, trait_009,
+    trait_010, trait_011, trait_012, trait_013, trait_014, trait_015, trait_016, trait_017, trait_018, trait_019,
+    trait_020, trait_021, trait_022, trait_023, trait_024, trait_025, trait_026, trait_027, trait_028, trait_029,
+    trait_030, trait_031, trait_032, trait_033, trait_034, trait_035, trait_036, trait_037, trait_038, trait_039,
+    trait_040, trait_041, trait_042, trait_043, trait_044, trait_045, trait_046, trait_047, trait_048, trait_049,
+    trait_050, trait_051, trait_052, trait_053, trait_054, trait_055, trait_056, trait_057, trait_058, trait_059,
+    trait_060, trait_061, trait_062, trait_063, trait_064, trait_065, trait_066, trait_067, trait_068, trait_069,
+    trait_070, trait_071, trait_072, trait_073, trait_074, trait_075, trait_076, trait_077, trait_078, trait_079,
+    trait_080, trait_081, trait_082, trait_083, trait_084, trait_085, trait_086, trait_087, trait_088, trait_089,
+    trait_090, trait_091, trait_092, trait_093, trait_094, trait_095, trait_096, trait_097, trait_098, trait_099,
+    trait_100, trait_101, trait_102, trait_103, trait_104, trait_105, trait_106, trait_107, trait_108, trait_109,
+    trait_110, trait_111, trait_112, trait_113, trait_114, trait_115, trait_116, trait_117, trait_118, trait_119,
+    trait_120, trait_121, trait_122, trait_123, trait_124, trait_125, trait_126, trait_127, trait_128, trait_129,
+)]
+#[derive(Serialize, Deserialize)]
+pub struct Huge {
+    id: u64,
+    adjectives: HugeAdjectives,
+    others: Vec<Huge>,
+}
+
+fn main() {
+    let a = SmallAdjectives::BOLD | SmallAdjectives::CALM;
+    let b = SmallAdjectives::CALM | SmallAdjectives::SHY;
+    assert_eq!(a.count(), 2);
+    assert_eq!(SmallAdjectives::all().count(), 4);
+    assert_eq!(a.similarity(&b), Some(1.0 / 3.0));
+    assert_eq!(a.similarity(&a), Some(1.0));
+    assert_eq!(SmallAdjectives::empty().similarity(&SmallAdjectives::empty()), None);
+
+    let a = HugeAdjectives::TRAIT_000 | HugeAdjectives::TRAIT_064 | HugeAdjectives::TRAIT_129;
+    let b = HugeAdjectives::TRAIT_064 | HugeAdjectives::TRAIT_129;
+    assert_eq!(a.count(), 3);
+    assert_eq!(HugeAdjectives::all().count(), 130);
+    assert_eq!(a.similarity(&b), Some(2.0 / 3.0));
+}
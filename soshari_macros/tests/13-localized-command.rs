@@ -0,0 +1,35 @@
+use serenity::model::prelude::UserId;
+use soshari_macros::SlashCommand;
+
+#[derive(SlashCommand)]
+#[command(name = "foo", description = "The foo command")]
+#[localized(locale = "de", description = "Der Foo-Befehl")]
+#[localized(locale = "fr", name = "truc", description = "La commande truc")]
+pub enum FooCommand {
+    #[command(description = "Start the foo test")]
+    #[localized(locale = "de", name = "starten", description = "Starte den Foo-Test")]
+    Start {
+        #[option(description = "User to contribute to")]
+        #[localized(locale = "de", name = "nutzer", description = "Nutzer, zu dem beigetragen wird")]
+        user: Option<UserId>,
+    },
+}
+
+fn main() {
+    let command = serde_json::to_value(FooCommand::create()).unwrap();
+
+    assert_eq!(command["name"], "foo");
+    assert_eq!(command["name_localizations"]["fr"], "truc");
+    assert!(command["name_localizations"].get("de").is_none());
+    assert_eq!(command["description_localizations"]["de"], "Der Foo-Befehl");
+    assert_eq!(command["description_localizations"]["fr"], "La commande truc");
+
+    let start = &command["options"][0];
+    assert_eq!(start["name"], "start");
+    assert_eq!(start["name_localizations"]["de"], "starten");
+    assert_eq!(start["description_localizations"]["de"], "Starte den Foo-Test");
+    assert_eq!(start["options"][0]["name"], "user");
+    assert_eq!(start["options"][0]["name_localizations"]["de"], "nutzer");
+
+    let _ = FooCommand::parse;
+}
@@ -0,0 +1,23 @@
+use serenity::model::prelude::UserId;
+use soshari_macros::SlashCommand;
+
+#[derive(SlashCommand)]
+#[command(name = "foo", description = "The foo command")]
+pub enum FooCommand {
+    #[command(description = "Start the foo test")]
+    Start {
+        #[option(description = "User to contribute to")]
+        user: Option<UserId>,
+        #[option(description = "Server to contribute from", autocomplete = true)]
+        guild: Option<String>,
+    },
+}
+
+fn main() {
+    let command = serde_json::to_value(FooCommand::create()).unwrap();
+
+    let options = command["options"][0]["options"].as_array().unwrap();
+    assert_ne!(options[0]["autocomplete"], true);
+    assert_eq!(options[1]["name"], "guild");
+    assert_eq!(options[1]["autocomplete"], true);
+}
@@ -0,0 +1,14 @@
+use serenity::model::prelude::UserId;
+use soshari_macros::SlashCommand;
+
+#[derive(SlashCommand)]
+#[command(name = "foo", description = "The foo command")]
+pub enum FooCommand {
+    #[command(description = "Query for a user")]
+    Query {
+        #[option(description = "User to query", autocomplete = true)]
+        user: UserId,
+    },
+}
+
+fn main() {}
@@ -2,6 +2,20 @@
 fn tests() {
     let t = trybuild::TestCases::new();
     t.pass("tests/01-simple-proc.rs");
+    t.pass("tests/02-slash-command.rs");
+    t.compile_fail("tests/03-slash-command-tuple-variant.rs");
+    t.compile_fail("tests/04-slash-command-unsupported-type.rs");
+    t.pass("tests/05-adjective-metadata.rs");
+    t.pass("tests/06-many-adjectives.rs");
+    t.compile_fail("tests/07-invalid-adjectives.rs");
+    t.pass("tests/08-stable-encoding.rs");
+    t.compile_fail("tests/09-reused-retired-adjective.rs");
+    t.pass("tests/10-versioned-store.rs");
+    t.pass("tests/11-slash-command-group.rs");
+    t.pass("tests/12-similarity.rs");
+    t.pass("tests/13-localized-command.rs");
+    t.pass("tests/14-slash-command-autocomplete.rs");
+    t.compile_fail("tests/15-autocomplete-unsupported-type.rs");
     // t.pass("tests/02-parser.rs");
     // t.pass("tests/03-missing-positional.rs");
     // t.pass("tests/04-derive-enum.rs");
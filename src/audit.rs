@@ -0,0 +1,370 @@
+use std::{
+    fmt, fs,
+    io::{self, Read},
+    sync::Arc,
+};
+
+use serde::{Deserialize, Serialize};
+use serenity::{
+    builder::{CreateEmbed, CreateMessage},
+    model::{
+        prelude::{ChannelId, GuildId, UserId},
+        Timestamp,
+    },
+    prelude::*,
+};
+
+use crate::{commands::util::lock, locale, settings::Settings, store};
+
+pub const STORE: &str = "audit";
+/// Number of events kept; the oldest ones are dropped as new ones are recorded.
+const MAX_EVENTS: usize = 5000;
+/// Size in bytes past which the next record compacts the store down to the latest
+/// [`MAX_EVENTS`]; below it, events are only appended.
+const MAX_SIZE: u64 = 4 * 1024 * 1024;
+
+/// Serializes writes to the audit store across handlers.
+pub struct AuditLock;
+
+impl TypeMapKey for AuditLock {
+    type Value = Arc<Mutex<()>>;
+}
+
+/// Something done to window data, by the user of the enclosing [`Event`].
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Action {
+    SelfAssessmentUpdated {
+        window: String,
+    },
+    /// Recorded without its author when the window's contributions are anonymous.
+    ContributionAdded {
+        window: String,
+        target: u64,
+    },
+    ContributionRemoved {
+        window: String,
+        target: u64,
+        contributor: u64,
+    },
+    ContributionsListed {
+        window: String,
+        target: u64,
+    },
+    WindowWiped {
+        window: String,
+        target: u64,
+    },
+    UserBanned {
+        target: u64,
+    },
+    UserUnbanned {
+        target: u64,
+    },
+    LogChannelSet {
+        channel: u64,
+    },
+    SetUpdated {
+        set: String,
+        change: String,
+    },
+    /// Recorded without its author, who stays anonymous like the comment itself.
+    CommentLeft {
+        window: String,
+        target: u64,
+    },
+    CommentsRemoved {
+        window: String,
+        target: u64,
+        count: usize,
+    },
+    CommentsAllowed {
+        allowed: bool,
+    },
+}
+
+impl Action {
+    pub fn title(&self) -> &'static str {
+        match self {
+            Action::SelfAssessmentUpdated { .. } => "Self-assessment updated",
+            Action::ContributionAdded { .. } => "Contribution added",
+            Action::ContributionRemoved { .. } => "Contribution removed",
+            Action::ContributionsListed { .. } => "Contributions listed",
+            Action::WindowWiped { .. } => "Window wiped",
+            Action::UserBanned { .. } => "User banned",
+            Action::UserUnbanned { .. } => "User unbanned",
+            Action::LogChannelSet { .. } => "Log channel set",
+            Action::SetUpdated { .. } => "Adjective set updated",
+            Action::CommentLeft { .. } => "Comment left",
+            Action::CommentsRemoved { .. } => "Comments removed",
+            Action::CommentsAllowed { .. } => "Comment setting changed",
+        }
+    }
+
+    /// The action described for `locale`, mentioning the users involved.
+    pub fn description(&self, locale: &str) -> String {
+        let (text, args): (&str, Vec<String>) = match self {
+            Action::SelfAssessmentUpdated { window } => {
+                ("Submitted a {} self-assessment", vec![window.clone()])
+            }
+            Action::ContributionAdded { window, target } => (
+                "Contributed to the {} window of {}",
+                vec![window.clone(), format!("<@{target}>")],
+            ),
+            Action::ContributionRemoved {
+                window,
+                target,
+                contributor,
+            } => (
+                "Removed the contribution of {} to the {} window of {}",
+                vec![
+                    format!("<@{contributor}>"),
+                    window.clone(),
+                    format!("<@{target}>"),
+                ],
+            ),
+            Action::ContributionsListed { window, target } => (
+                "Listed the contributions to the {} window of {}",
+                vec![window.clone(), format!("<@{target}>")],
+            ),
+            Action::WindowWiped { window, target } => (
+                "Deleted the {} window of {}",
+                vec![window.clone(), format!("<@{target}>")],
+            ),
+            Action::UserBanned { target } => (
+                "{} can no longer contribute to windows",
+                vec![format!("<@{target}>")],
+            ),
+            Action::UserUnbanned { target } => (
+                "{} can contribute to windows again",
+                vec![format!("<@{target}>")],
+            ),
+            Action::LogChannelSet { channel } => (
+                "Window activity is logged to {}",
+                vec![format!("<#{channel}>")],
+            ),
+            Action::SetUpdated { set, change } => (
+                "Adjective set {}: {}",
+                vec![format!("`{set}`"), change.clone()],
+            ),
+            Action::CommentLeft { window, target } => (
+                "A comment was left on the {} window of {}",
+                vec![window.clone(), format!("<@{target}>")],
+            ),
+            Action::CommentsRemoved {
+                window,
+                target,
+                count,
+            } => (
+                "Removed comments from the {} window of {} ({})",
+                vec![window.clone(), format!("<@{target}>"), count.to_string()],
+            ),
+            Action::CommentsAllowed { allowed: true } => {
+                ("Contributions from this server may carry comments", vec![])
+            }
+            Action::CommentsAllowed { allowed: false } => (
+                "Contributions from this server no longer carry comments",
+                vec![],
+            ),
+        };
+        let args = args
+            .iter()
+            .map(|arg| arg as &dyn fmt::Display)
+            .collect::<Vec<_>>();
+        locale::format(locale, text, &args)
+    }
+
+    /// The user the action was done to, if any.
+    pub fn target(&self) -> Option<u64> {
+        match self {
+            Action::ContributionAdded { target, .. }
+            | Action::ContributionRemoved { target, .. }
+            | Action::ContributionsListed { target, .. }
+            | Action::WindowWiped { target, .. }
+            | Action::UserBanned { target }
+            | Action::UserUnbanned { target }
+            | Action::CommentLeft { target, .. }
+            | Action::CommentsRemoved { target, .. } => Some(*target),
+            Action::SelfAssessmentUpdated { .. }
+            | Action::LogChannelSet { .. }
+            | Action::SetUpdated { .. }
+            | Action::CommentsAllowed { .. } => None,
+        }
+    }
+}
+
+/// An entry of the audit log; `guild_id` is the guild the action was taken from.
+#[derive(Serialize, Deserialize)]
+pub struct Event {
+    pub timestamp: u64,
+    pub guild_id: Option<u64>,
+    /// Who performed the action, `None` for anonymous actions.
+    pub user: Option<u64>,
+    #[serde(flatten)]
+    pub action: Action,
+}
+
+impl Event {
+    /// Whether `user` performed the action or was its target.
+    pub fn involves(&self, user: u64) -> bool {
+        self.user == Some(user) || self.action.target() == Some(user)
+    }
+}
+
+/// The latest [`MAX_EVENTS`] recorded events, oldest first.
+pub fn events() -> io::Result<Vec<Event>> {
+    let mut events = parse(&store::read(STORE)?)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+    let expired = events.len().saturating_sub(MAX_EVENTS);
+    events.drain(..expired);
+    Ok(events)
+}
+
+/// Parses the audit store, one event per line. Stores written before events were
+/// appended hold a single JSON array instead.
+fn parse(contents: &str) -> serde_json::Result<Vec<Event>> {
+    if contents.trim_start().starts_with('[') {
+        return serde_json::from_str(contents);
+    }
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(serde_json::from_str)
+        .collect()
+}
+
+fn serialize(events: &[Event]) -> serde_json::Result<String> {
+    let mut contents = String::new();
+    for event in events {
+        contents.push_str(&serde_json::to_string(event)?);
+        contents.push('\n');
+    }
+    Ok(contents)
+}
+
+/// Whether the audit store has outgrown [`MAX_SIZE`] or still holds a JSON array, so
+/// it must be rewritten rather than appended to.
+fn needs_compaction() -> io::Result<bool> {
+    let mut file = fs::File::open(store::path(STORE))?;
+    if file.metadata()?.len() > MAX_SIZE {
+        return Ok(true);
+    }
+    let mut first = [0; 1];
+    Ok(file.read(&mut first)? == 1 && first[0] == b'[')
+}
+
+/// Adds `event` to the audit store, appending it unless the store needs compacting.
+fn save(event: Event) -> io::Result<()> {
+    if !needs_compaction()? {
+        return store::append(STORE, serialize(&[event])?.as_bytes());
+    }
+    let mut events = events()?;
+    push(&mut events, event);
+    store::write(STORE, serialize(&events)?.as_bytes())
+}
+
+/// Appends `event` to `events`, dropping the oldest events beyond [`MAX_EVENTS`].
+fn push(events: &mut Vec<Event>, event: Event) {
+    events.push(event);
+    let expired = events.len().saturating_sub(MAX_EVENTS);
+    events.drain(..expired);
+}
+
+/// Persists `action` by `user` to the audit store and posts it to the guild's log
+/// channel, if one is set. Anonymous actions have no `user`. Failures are only printed
+/// so they never block the action.
+pub async fn record(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    user: Option<UserId>,
+    action: Action,
+) {
+    let event = Event {
+        timestamp: store::now(),
+        guild_id: guild_id.map(u64::from),
+        user: user.map(u64::from),
+        action: action.clone(),
+    };
+
+    let lock = lock::<AuditLock>(ctx).await;
+    let saved = {
+        let _guard = lock.lock().await;
+        save(event)
+    };
+    if let Err(error) = saved {
+        eprintln!("Cannot save the audit log: {error}");
+        return;
+    }
+
+    let guild_id = match guild_id {
+        Some(guild_id) => guild_id,
+        None => return,
+    };
+    let channel = match Settings::load() {
+        Ok(settings) => settings.guild(guild_id).and_then(|guild| guild.log_channel),
+        Err(error) => {
+            eprintln!("Cannot load the guild settings: {error}");
+            return;
+        }
+    };
+    let channel = match channel {
+        Some(channel) => ChannelId::new(channel),
+        None => return,
+    };
+
+    // Log channels are read by the whole server, so they follow its language.
+    let locale = guild_id
+        .to_guild_cached(&ctx.cache)
+        .map(|guild| guild.preferred_locale.clone())
+        .unwrap_or_else(|| locale::DEFAULT.to_string());
+    let mut description = action.description(&locale);
+    if let Some(user) = user {
+        description.push('\n');
+        description.push_str(&locale::format(&locale, "By {}", &[&format!("<@{user}>")]));
+    }
+    let embed = CreateEmbed::new()
+        .title(locale::tr(&locale, action.title()))
+        .description(description)
+        .color((0xFF, 0x5C, 0x5C))
+        .timestamp(Timestamp::now());
+    if let Err(error) = channel
+        .send_message(&ctx.http, CreateMessage::new().embed(embed))
+        .await
+    {
+        eprintln!("Cannot post to the log channel of {guild_id}: {error}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(timestamp: u64) -> Event {
+        Event {
+            timestamp,
+            guild_id: None,
+            user: None,
+            action: Action::LogChannelSet { channel: 1 },
+        }
+    }
+
+    #[test]
+    fn drops_the_oldest_events() {
+        let mut events = (0..MAX_EVENTS as u64).map(event).collect();
+        push(&mut events, event(MAX_EVENTS as u64));
+        assert_eq!(events.len(), MAX_EVENTS);
+        assert_eq!(events[0].timestamp, 1);
+        assert_eq!(events.last().unwrap().timestamp, MAX_EVENTS as u64);
+    }
+
+    #[test]
+    fn parses_appended_and_array_stores() {
+        let events = [event(1), event(2)];
+        let appended = parse(&serialize(&events).unwrap()).unwrap();
+        let array = parse(&serde_json::to_string_pretty(&events).unwrap()).unwrap();
+        for parsed in [appended, array] {
+            let timestamps: Vec<_> = parsed.iter().map(|event| event.timestamp).collect();
+            assert_eq!(timestamps, [1, 2]);
+        }
+    }
+}
@@ -0,0 +1,763 @@
+use std::io;
+
+use itertools::Itertools;
+
+use serenity::prelude::*;
+use serenity::{
+    builder::{CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage},
+    model::prelude::{ChannelId, CommandInteraction, GuildId, UserId},
+};
+use soshari_macros::SlashCommand;
+
+use crate::{
+    audit::{self, Action},
+    locale,
+    sets::{Sets, SetsLock},
+    settings::{Settings, SettingsLock},
+};
+
+use super::{
+    competency::CompetencyAdjectives,
+    custom,
+    engine::{self, Kind},
+    johari::JohariAdjectives,
+    nohari::NohariAdjectives,
+    util::{
+        can_manage_guild, lock, mentions, respond_autocomplete, respond_embed_error, Visibility,
+    },
+    values::ValuesAdjectives,
+};
+
+#[derive(SlashCommand)]
+#[command(name = "admin", description = "Moderate window data")]
+#[localized(locale = "de", description = "Moderiere Fensterdaten")]
+pub enum AdminCommand {
+    #[command(description = "List the users who contributed to a window")]
+    #[localized(
+        locale = "de",
+        description = "Liste die Nutzer auf, die zu einem Fenster beigetragen haben"
+    )]
+    Contributions {
+        #[option(description = "User whose window to list")]
+        #[localized(
+            locale = "de",
+            description = "Nutzer, dessen Beitragende aufgelistet werden"
+        )]
+        user: UserId,
+        #[option(
+            description = "Window type: johari, nohari, competency, values or an adjective set",
+            autocomplete = true
+        )]
+        #[localized(
+            locale = "de",
+            description = "Fenstertyp: johari, nohari, competency, values oder eine Adjektivliste"
+        )]
+        window: String,
+    },
+    #[command(description = "Remove a user's contribution to a window")]
+    #[localized(
+        locale = "de",
+        description = "Entferne den Beitrag eines Nutzers zu einem Fenster"
+    )]
+    Remove {
+        #[option(description = "User whose window to remove the contribution from")]
+        #[localized(
+            locale = "de",
+            description = "Nutzer, aus dessen Fenster der Beitrag entfernt wird"
+        )]
+        user: UserId,
+        #[option(description = "User whose contribution to remove")]
+        #[localized(locale = "de", description = "Nutzer, dessen Beitrag entfernt wird")]
+        contributor: UserId,
+        #[option(
+            description = "Window type: johari, nohari, competency, values or an adjective set",
+            autocomplete = true
+        )]
+        #[localized(
+            locale = "de",
+            description = "Fenstertyp: johari, nohari, competency, values oder eine Adjektivliste"
+        )]
+        window: String,
+        #[option(
+            description = "Confirm deleting data shared by every server, required for the built-in windows"
+        )]
+        #[localized(
+            locale = "de",
+            description = "Bestätige das Löschen von Daten aller Server, nötig für die eingebauten Fenster"
+        )]
+        confirm: Option<bool>,
+    },
+    #[command(
+        name = "remove-comments",
+        description = "Remove the comments left on a user's window"
+    )]
+    #[localized(
+        locale = "de",
+        description = "Entferne die Kommentare im Fenster eines Nutzers"
+    )]
+    RemoveComments {
+        #[option(description = "User whose window to remove comments from")]
+        #[localized(
+            locale = "de",
+            description = "Nutzer, aus dessen Fenster Kommentare entfernt werden"
+        )]
+        user: UserId,
+        #[option(
+            description = "Window type: johari, nohari, competency, values or an adjective set",
+            autocomplete = true
+        )]
+        #[localized(
+            locale = "de",
+            description = "Fenstertyp: johari, nohari, competency, values oder eine Adjektivliste"
+        )]
+        window: String,
+        #[option(
+            description = "Number of the comment as listed by the Comments button, every comment by default"
+        )]
+        #[localized(
+            locale = "de",
+            description = "Nummer des Kommentars laut dem Knopf Kommentare, standardmäßig alle Kommentare"
+        )]
+        number: Option<i64>,
+        #[option(
+            description = "Confirm deleting data shared by every server, required for the built-in windows"
+        )]
+        #[localized(
+            locale = "de",
+            description = "Bestätige das Löschen von Daten aller Server, nötig für die eingebauten Fenster"
+        )]
+        confirm: Option<bool>,
+    },
+    #[command(description = "Allow or disallow comments on contributions made from this server")]
+    #[localized(
+        locale = "de",
+        description = "Erlaube oder verbiete Kommentare zu Beiträgen von diesem Server"
+    )]
+    Comments {
+        #[option(description = "Whether contributions may carry comments")]
+        #[localized(locale = "de", description = "Ob Beiträge Kommentare enthalten dürfen")]
+        allowed: bool,
+    },
+    #[command(description = "Delete a user's window and every contribution to it")]
+    #[localized(
+        locale = "de",
+        description = "Lösche das Fenster eines Nutzers und alle Beiträge dazu"
+    )]
+    Wipe {
+        #[option(description = "User whose window to delete")]
+        #[localized(locale = "de", description = "Nutzer, dessen Fenster gelöscht wird")]
+        user: UserId,
+        #[option(
+            description = "Window type: johari, nohari, competency, values or an adjective set",
+            autocomplete = true
+        )]
+        #[localized(
+            locale = "de",
+            description = "Fenstertyp: johari, nohari, competency, values oder eine Adjektivliste"
+        )]
+        window: String,
+        #[option(
+            description = "Confirm deleting data shared by every server, required for the built-in windows"
+        )]
+        #[localized(
+            locale = "de",
+            description = "Bestätige das Löschen von Daten aller Server, nötig für die eingebauten Fenster"
+        )]
+        confirm: Option<bool>,
+    },
+    #[command(description = "Ban a user from contributing to windows in this server")]
+    #[localized(
+        locale = "de",
+        description = "Schließe einen Nutzer auf diesem Server vom Beitragen zu Fenstern aus"
+    )]
+    Ban {
+        #[option(description = "User to ban")]
+        #[localized(locale = "de", description = "Auszuschließender Nutzer")]
+        user: UserId,
+    },
+    #[command(description = "Allow a banned user to contribute again")]
+    #[localized(
+        locale = "de",
+        description = "Erlaube einem ausgeschlossenen Nutzer wieder Beiträge"
+    )]
+    Unban {
+        #[option(description = "User to unban")]
+        #[localized(
+            locale = "de",
+            description = "Nutzer, dessen Ausschluss aufgehoben wird"
+        )]
+        user: UserId,
+    },
+    #[command(
+        name = "log-channel",
+        description = "Set the channel window activity is logged to"
+    )]
+    #[localized(
+        locale = "de",
+        description = "Lege den Kanal fest, in dem Fensteraktivitäten protokolliert werden"
+    )]
+    LogChannel {
+        #[option(description = "Channel to log to")]
+        #[localized(locale = "de", description = "Protokollkanal")]
+        channel: ChannelId,
+    },
+    #[command(description = "Show the latest audit log entries of this server")]
+    #[localized(
+        locale = "de",
+        description = "Zeige die neuesten Einträge im Prüfprotokoll dieses Servers"
+    )]
+    Audit {
+        #[option(description = "Only show entries involving this user")]
+        #[localized(
+            locale = "de",
+            description = "Zeige nur Einträge, die diesen Nutzer betreffen"
+        )]
+        user: Option<UserId>,
+    },
+}
+
+/// The built-in window types, whose data is shared by every guild.
+const WINDOWS: [&str; 4] = ["johari", "nohari", "competency", "values"];
+
+/// The moderation operations of a window type.
+struct Window {
+    /// Whether the data is shared by every guild rather than kept per guild.
+    shared: bool,
+    /// Whether contributions are anonymous, so contributors are not listed.
+    anonymous: bool,
+    contributions: Box<dyn Fn(UserId) -> io::Result<Option<Vec<u64>>> + Send + Sync>,
+    remove_contribution: Box<dyn Fn(UserId, UserId) -> io::Result<bool> + Send + Sync>,
+    remove_comments: Box<dyn Fn(UserId, Option<usize>) -> io::Result<usize> + Send + Sync>,
+    wipe: Box<dyn Fn(UserId) -> io::Result<bool> + Send + Sync>,
+}
+
+/// The operations of the built-in window type `name`, or of the adjective set `name` of
+/// guild `guild_id`.
+fn window(guild_id: GuildId, name: &str) -> io::Result<Option<Window>> {
+    Ok(match name {
+        "johari" => Some(Window {
+            shared: true,
+            anonymous: JohariAdjectives::ANONYMOUS,
+            contributions: Box::new(engine::contributions::<JohariAdjectives>),
+            remove_contribution: Box::new(engine::remove_contribution::<JohariAdjectives>),
+            remove_comments: Box::new(engine::remove_comments::<JohariAdjectives>),
+            wipe: Box::new(engine::wipe::<JohariAdjectives>),
+        }),
+        "nohari" => Some(Window {
+            shared: true,
+            anonymous: NohariAdjectives::ANONYMOUS,
+            contributions: Box::new(engine::contributions::<NohariAdjectives>),
+            remove_contribution: Box::new(engine::remove_contribution::<NohariAdjectives>),
+            remove_comments: Box::new(engine::remove_comments::<NohariAdjectives>),
+            wipe: Box::new(engine::wipe::<NohariAdjectives>),
+        }),
+        "competency" => Some(Window {
+            shared: true,
+            anonymous: CompetencyAdjectives::ANONYMOUS,
+            contributions: Box::new(engine::contributions::<CompetencyAdjectives>),
+            remove_contribution: Box::new(engine::remove_contribution::<CompetencyAdjectives>),
+            remove_comments: Box::new(engine::remove_comments::<CompetencyAdjectives>),
+            wipe: Box::new(engine::wipe::<CompetencyAdjectives>),
+        }),
+        "values" => Some(Window {
+            shared: true,
+            anonymous: ValuesAdjectives::ANONYMOUS,
+            contributions: Box::new(engine::contributions::<ValuesAdjectives>),
+            remove_contribution: Box::new(engine::remove_contribution::<ValuesAdjectives>),
+            remove_comments: Box::new(engine::remove_comments::<ValuesAdjectives>),
+            wipe: Box::new(engine::wipe::<ValuesAdjectives>),
+        }),
+        name if Sets::load()?.get(guild_id, name).is_some() => {
+            let (contributions, remove_contribution, remove_comments, wipe) = (
+                name.to_string(),
+                name.to_string(),
+                name.to_string(),
+                name.to_string(),
+            );
+            Some(Window {
+                shared: false,
+                anonymous: false,
+                contributions: Box::new(move |target| {
+                    custom::contributions(guild_id, &contributions, target)
+                }),
+                remove_contribution: Box::new(move |target, contributor| {
+                    custom::remove_contribution(guild_id, &remove_contribution, target, contributor)
+                }),
+                remove_comments: Box::new(move |target, number| {
+                    custom::remove_comments(guild_id, &remove_comments, target, number)
+                }),
+                wipe: Box::new(move |target| custom::wipe(guild_id, &wipe, target)),
+            })
+        }
+        _ => None,
+    })
+}
+
+/// Suggests the window types containing the typed text: the built-in ones and the
+/// adjective sets of the guild.
+pub async fn autocomplete(ctx: Context, interaction: CommandInteraction) {
+    let partial = match interaction.data.autocomplete() {
+        Some(option) if option.name == "window" => option.value.to_lowercase(),
+        _ => return,
+    };
+    let mut names = WINDOWS.iter().map(ToString::to_string).collect::<Vec<_>>();
+    if let Some(guild_id) = interaction.guild_id {
+        match Sets::load() {
+            Ok(sets) => names.extend(sets.of(guild_id).map(|set| set.name.clone())),
+            Err(error) => eprintln!("Cannot load the adjective sets: {error}"),
+        }
+    }
+    let choices = names
+        .into_iter()
+        .filter(|name| name.contains(&partial))
+        .take(25)
+        .map(|name| (name.clone(), name))
+        .collect();
+    respond_autocomplete(&ctx.http, &interaction, choices).await;
+}
+
+pub async fn run(ctx: Context, command: CommandInteraction, admin: AdminCommand) {
+    let guild_id = match command.guild_id {
+        Some(guild_id) => guild_id,
+        None => {
+            respond_embed_error(
+                &ctx.http,
+                command,
+                "Admin commands can only be used in a server",
+                Visibility::Private,
+            )
+            .await;
+            return;
+        }
+    };
+    if !can_manage_guild(&command) {
+        respond_embed_error(
+            &ctx.http,
+            command,
+            "You need the Manage Server permission to moderate window data",
+            Visibility::Private,
+        )
+        .await;
+        return;
+    }
+
+    if let AdminCommand::Audit { user } = admin {
+        show_audit(ctx, command, guild_id, user).await;
+        return;
+    }
+
+    // Window data is shared between servers, so moderators only act on their own members.
+    // Built-in window data is shared too, so deleting it takes an explicit confirmation.
+    let target = match &admin {
+        AdminCommand::Contributions { user, window } => Some((*user, window.as_str(), true)),
+        AdminCommand::Remove {
+            user,
+            window,
+            confirm,
+            ..
+        }
+        | AdminCommand::RemoveComments {
+            user,
+            window,
+            confirm,
+            ..
+        }
+        | AdminCommand::Wipe {
+            user,
+            window,
+            confirm,
+        } => Some((*user, window.as_str(), *confirm == Some(true))),
+        _ => None,
+    };
+    let operations = match target {
+        Some((user, name, confirmed)) => {
+            if guild_id.member(&ctx.http, user).await.is_err() {
+                respond_embed_error(
+                    &ctx.http,
+                    command,
+                    "The user is not a member of this server",
+                    Visibility::Private,
+                )
+                .await;
+                return;
+            }
+            match window(guild_id, name) {
+                Ok(Some(window)) if window.shared && !confirmed => {
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "This window type is shared by every server, set confirm to delete its data everywhere",
+                        Visibility::Private,
+                    )
+                    .await;
+                    return;
+                }
+                Ok(Some(window)) => Some(window),
+                Ok(None) => {
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "Unknown window type, expected johari, nohari, competency, values or an adjective set",
+                        Visibility::Private,
+                    )
+                    .await;
+                    return;
+                }
+                Err(error) => {
+                    eprintln!("Cannot load the adjective sets: {error}");
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "The adjective sets could not be read",
+                        Visibility::Private,
+                    )
+                    .await;
+                    return;
+                }
+            }
+        }
+        None => None,
+    };
+
+    let anonymous = operations.as_ref().is_some_and(|window| window.anonymous);
+    // Adjective sets are also changed by their owners, so their lock is held across the
+    // operation.
+    let sets_lock = lock::<SetsLock>(&ctx).await;
+    let sets_guard = match &operations {
+        Some(window) if !window.shared => Some(sets_lock.lock().await),
+        _ => None,
+    };
+    let (action, contributors) = match (admin, operations) {
+        (AdminCommand::Contributions { user, window }, Some(operations)) => {
+            match (operations.contributions)(user) {
+                Ok(Some(contributors)) => (
+                    Action::ContributionsListed {
+                        window,
+                        target: user.into(),
+                    },
+                    Some(contributors),
+                ),
+                Ok(None) => {
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "The user has no window",
+                        Visibility::Private,
+                    )
+                    .await;
+                    return;
+                }
+                Err(error) => {
+                    eprintln!("Cannot load the {window} database: {error}");
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "The window database could not be read",
+                        Visibility::Private,
+                    )
+                    .await;
+                    return;
+                }
+            }
+        }
+        (
+            AdminCommand::Remove {
+                user,
+                contributor,
+                window,
+                ..
+            },
+            Some(operations),
+        ) => match (operations.remove_contribution)(user, contributor) {
+            Ok(true) => (
+                Action::ContributionRemoved {
+                    window,
+                    target: user.into(),
+                    contributor: contributor.into(),
+                },
+                None,
+            ),
+            Ok(false) => {
+                respond_embed_error(
+                    &ctx.http,
+                    command,
+                    "Cannot find the contribution",
+                    Visibility::Private,
+                )
+                .await;
+                return;
+            }
+            Err(error) => {
+                eprintln!("Cannot update the {window} database: {error}");
+                respond_embed_error(
+                    &ctx.http,
+                    command,
+                    "The window database could not be updated",
+                    Visibility::Private,
+                )
+                .await;
+                return;
+            }
+        },
+        (
+            AdminCommand::RemoveComments {
+                user,
+                window,
+                number,
+                ..
+            },
+            Some(operations),
+        ) => {
+            // Numbers are those shown by the Comments button, counting from 1.
+            let number = match number.map(usize::try_from) {
+                None => None,
+                Some(Ok(number)) if number > 0 => Some(number),
+                Some(_) => {
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "Comment numbers start at 1",
+                        Visibility::Private,
+                    )
+                    .await;
+                    return;
+                }
+            };
+            match (operations.remove_comments)(user, number) {
+                Ok(0) => {
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "Cannot find the comment",
+                        Visibility::Private,
+                    )
+                    .await;
+                    return;
+                }
+                Ok(count) => (
+                    Action::CommentsRemoved {
+                        window,
+                        target: user.into(),
+                        count,
+                    },
+                    None,
+                ),
+                Err(error) => {
+                    eprintln!("Cannot update the {window} database: {error}");
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "The window database could not be updated",
+                        Visibility::Private,
+                    )
+                    .await;
+                    return;
+                }
+            }
+        }
+        (AdminCommand::Wipe { user, window, .. }, Some(operations)) => {
+            match (operations.wipe)(user) {
+                Ok(true) => (
+                    Action::WindowWiped {
+                        window,
+                        target: user.into(),
+                    },
+                    None,
+                ),
+                Ok(false) => {
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "The user has no window",
+                        Visibility::Private,
+                    )
+                    .await;
+                    return;
+                }
+                Err(error) => {
+                    eprintln!("Cannot update the {window} database: {error}");
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "The window database could not be updated",
+                        Visibility::Private,
+                    )
+                    .await;
+                    return;
+                }
+            }
+        }
+        (admin, _) => {
+            let lock = lock::<SettingsLock>(&ctx).await;
+            let guard = lock.lock().await;
+            let mut settings = match Settings::load() {
+                Ok(settings) => settings,
+                Err(error) => {
+                    drop(guard);
+                    eprintln!("Cannot load the guild settings: {error}");
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "The guild settings could not be read",
+                        Visibility::Private,
+                    )
+                    .await;
+                    return;
+                }
+            };
+            let guild = settings.guild_mut(guild_id);
+
+            let action = match admin {
+                AdminCommand::Ban { user } => {
+                    if !guild.banned.contains(&u64::from(user)) {
+                        guild.banned.push(user.into());
+                    }
+                    Action::UserBanned {
+                        target: user.into(),
+                    }
+                }
+                AdminCommand::Unban { user } => {
+                    guild.banned.retain(|id| *id != u64::from(user));
+                    Action::UserUnbanned {
+                        target: user.into(),
+                    }
+                }
+                AdminCommand::Comments { allowed } => {
+                    guild.comments_disabled = !allowed;
+                    Action::CommentsAllowed { allowed }
+                }
+                AdminCommand::LogChannel { channel } => {
+                    guild.log_channel = Some(channel.into());
+                    Action::LogChannelSet {
+                        channel: channel.into(),
+                    }
+                }
+                _ => {
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "The program reached a (supposedly) unreachable state. Something went wrong",
+                        Visibility::Private,
+                    )
+                    .await;
+                    panic!("Unreachable state (admin matching error)");
+                }
+            };
+
+            let saved = settings.dump();
+            drop(guard);
+            if let Err(error) = saved {
+                eprintln!("Cannot save the guild settings: {error}");
+                respond_embed_error(
+                    &ctx.http,
+                    command,
+                    "The guild settings could not be saved",
+                    Visibility::Private,
+                )
+                .await;
+                return;
+            }
+            (action, None)
+        }
+    };
+    drop(sets_guard);
+
+    let locale = command.locale.as_str();
+    let mut embed = CreateEmbed::new()
+        .title(locale::tr(locale, action.title()))
+        .description(action.description(locale))
+        .color((0xFF, 0x5C, 0x5C));
+    if let Some(contributors) = contributors {
+        embed = embed.field(
+            format!(
+                "{} ({})",
+                locale::tr(locale, "Contributors"),
+                contributors.len()
+            ),
+            if anonymous {
+                locale::tr(locale, "Contributions to this window are anonymous").to_string()
+            } else {
+                mentions(&contributors)
+            },
+            false,
+        );
+    }
+    audit::record(&ctx, Some(guild_id), Some(command.user.id), action).await;
+
+    command
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .embed(embed)
+                    .ephemeral(true),
+            ),
+        )
+        .await
+        .unwrap();
+}
+
+/// Lists the latest audit events of the guild, optionally only those involving `user`.
+async fn show_audit(
+    ctx: Context,
+    command: CommandInteraction,
+    guild_id: GuildId,
+    user: Option<UserId>,
+) {
+    let events = match audit::events() {
+        Ok(events) => events,
+        Err(error) => {
+            eprintln!("Cannot load the audit log: {error}");
+            respond_embed_error(
+                &ctx.http,
+                command,
+                "The audit log could not be read",
+                Visibility::Private,
+            )
+            .await;
+            return;
+        }
+    };
+
+    let entries = events
+        .iter()
+        .rev()
+        .filter(|event| event.guild_id == Some(guild_id.into()))
+        .filter(|event| user.is_none_or(|user| event.involves(user.into())))
+        .take(20)
+        .map(|event| match event.user {
+            Some(user) => format!(
+                "<t:{}:f> <@{user}> {}",
+                event.timestamp,
+                event.action.description(&command.locale)
+            ),
+            None => format!(
+                "<t:{}:f> {}",
+                event.timestamp,
+                event.action.description(&command.locale)
+            ),
+        })
+        .join("\n");
+
+    command
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .embed(
+                        CreateEmbed::new()
+                            .title(locale::tr(&command.locale, "Audit log"))
+                            .description(if entries.is_empty() {
+                                locale::tr(&command.locale, "No events have been recorded yet")
+                                    .to_string()
+                            } else {
+                                entries
+                            })
+                            .color((0xFF, 0x5C, 0x5C)),
+                    )
+                    .ephemeral(true),
+            ),
+        )
+        .await
+        .unwrap();
+}
@@ -0,0 +1,180 @@
+use serenity::prelude::*;
+use serenity::{
+    builder::{CreateActionRow, CreateButton, CreateEmbed},
+    model::prelude::{component::ButtonStyle, ComponentInteraction, GuildId, UserId},
+};
+
+use crate::{locale, settings};
+
+use super::{
+    competency::{self, CompetencyAdjectives},
+    custom, engine,
+    johari::{self, JohariAdjectives},
+    nohari::{self, NohariAdjectives},
+    util::{parse_user, respond_embed, respond_embed_error, Visibility},
+    values::{self, ValuesAdjectives},
+};
+
+/// Prefix of the custom ids of comment buttons, routed to [`run`] by the event handler.
+pub const PREFIX: &str = "comments:";
+/// Discord's limit on custom ids.
+const MAX_CUSTOM_ID: usize = 100;
+/// Room for comments in an embed description, under Discord's limit of 4096 characters
+/// to leave space for the count of hidden ones.
+const MAX_LENGTH: usize = 4000;
+
+/// A button revealing the `count` comments on `target`'s `window`, none without any
+/// comment or when `guild_id` disabled comments. The custom id names the window and
+/// target, so the button keeps working across restarts.
+pub fn buttons(
+    locale: &str,
+    guild_id: Option<GuildId>,
+    window: &str,
+    target: UserId,
+    count: usize,
+) -> Vec<CreateActionRow> {
+    let custom_id = format!("{PREFIX}{window}:{target}");
+    // Custom sets may have names too long to fit, their comments are then not linked.
+    if count == 0 || custom_id.len() > MAX_CUSTOM_ID {
+        return Vec::new();
+    }
+    match settings::comments_allowed(guild_id) {
+        Ok(true) => {}
+        Ok(false) => return Vec::new(),
+        Err(error) => {
+            eprintln!("Cannot load the guild settings: {error}");
+            return Vec::new();
+        }
+    }
+
+    let label = format!("{} ({count})", locale::tr(locale, "Comments"));
+    let button = CreateButton::new(label, custom_id).style(ButtonStyle::Secondary);
+    vec![CreateActionRow::Buttons(vec![button])]
+}
+
+/// Quotes as many of `comments` as fit in an embed description, numbered for moderators
+/// to remove them by, followed by how many were left out.
+fn quote(comments: &[String]) -> String {
+    let mut description = String::new();
+    let mut shown = 0;
+    for (index, comment) in comments.iter().enumerate() {
+        let quoted = format!(
+            "**{}.**\n> {}\n\n",
+            index + 1,
+            comment.replace('\n', "\n> ")
+        );
+        if description.chars().count() + quoted.chars().count() > MAX_LENGTH {
+            break;
+        }
+        description.push_str(&quoted);
+        shown += 1;
+    }
+    if shown < comments.len() {
+        description.push_str(&format!("(+{})", comments.len() - shown));
+    }
+    description.trim_end().to_string()
+}
+
+/// Shows the comments on the window named by the custom id of `component` to the user
+/// who clicked it. Comments never show who left them.
+pub async fn run(ctx: Context, component: ComponentInteraction) {
+    let parsed = component
+        .data
+        .custom_id
+        .strip_prefix(PREFIX)
+        .and_then(|rest| rest.rsplit_once(':'))
+        .and_then(|(window, target)| Some((window.to_string(), parse_user(target)?)));
+    let Some((window, target)) = parsed else {
+        eprintln!("Unknown comment button {}", component.data.custom_id);
+        return;
+    };
+
+    match settings::comments_allowed(component.guild_id) {
+        Ok(true) => {}
+        Ok(false) => {
+            respond_embed_error(
+                &ctx.http,
+                component,
+                "Comments are disabled in this server",
+                Visibility::Private,
+            )
+            .await;
+            return;
+        }
+        Err(error) => {
+            eprintln!("Cannot load the guild settings: {error}");
+            respond_embed_error(
+                &ctx.http,
+                component,
+                "The guild settings could not be read",
+                Visibility::Private,
+            )
+            .await;
+            return;
+        }
+    }
+
+    let comments = match window.as_str() {
+        johari::STORE => engine::comments::<JohariAdjectives>(target),
+        nohari::STORE => engine::comments::<NohariAdjectives>(target),
+        competency::STORE => engine::comments::<CompetencyAdjectives>(target),
+        values::STORE => engine::comments::<ValuesAdjectives>(target),
+        set => match component.guild_id {
+            Some(guild_id) => custom::comments(guild_id, set, target),
+            None => Ok(None),
+        },
+    };
+    let comments = match comments {
+        Ok(Some(comments)) => comments,
+        Ok(None) => {
+            respond_embed_error(
+                &ctx.http,
+                component,
+                "The user has no window",
+                Visibility::Private,
+            )
+            .await;
+            return;
+        }
+        Err(error) => {
+            eprintln!("Cannot load the {window} comments: {error}");
+            respond_embed_error(
+                &ctx.http,
+                component,
+                "The window database could not be read",
+                Visibility::Private,
+            )
+            .await;
+            return;
+        }
+    };
+
+    let locale = component.locale.as_str();
+    let embed = CreateEmbed::new()
+        .title(locale::tr(locale, "Comments"))
+        .description(if comments.is_empty() {
+            locale::tr(locale, "No comments yet").to_string()
+        } else {
+            quote(&comments)
+        });
+    respond_embed(&ctx.http, &component, embed, Visibility::Private).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_comments_that_fit() {
+        let comments = vec!["Kind\nand calm".to_string(), "Always on time".to_string()];
+        assert_eq!(
+            quote(&comments),
+            "**1.**\n> Kind\n> and calm\n\n**2.**\n> Always on time"
+        );
+
+        let comments = vec!["x".repeat(1500); 3];
+        let quoted = quote(&comments);
+        assert!(quoted.ends_with("(+1)"));
+        assert!(quoted.chars().count() <= 4096);
+    }
+}
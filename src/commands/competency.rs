@@ -0,0 +1,47 @@
+use soshari_macros::adjectives;
+
+use super::engine::{impl_window, window_command, Kind, Texts, Window};
+
+#[adjectives(
+    accountable, analytical, assertive, collaborative, communicative, creative, decisive, delegating,
+    detail_oriented, diplomatic, efficient, flexible, focused, goal_oriented, honest, innovative,
+    inspiring, methodical,
+    mentoring = { description = "Helps others grow in their role" },
+    motivated, negotiating, organised, persuasive, proactive,
+    public_speaking = { description = "Presents clearly in front of a group", emoji = "🎤" },
+    punctual, reliable, resilient, resourceful, self_directed, strategic, supportive, tactful,
+    thorough, versatile
+)]
+pub type Competency = Window<CompetencyAdjectives>;
+
+impl_window!(CompetencyAdjectives, CompetencyGroup);
+
+impl Kind for CompetencyAdjectives {
+    const STORE: &'static str = STORE;
+    const COLOR: (u8, u8, u8) = (0x5C, 0x9D, 0xFF);
+    const MIN: usize = 5;
+    const MAX: Option<usize> = Some(8);
+    const TEXTS: Texts = Texts {
+        test: "The Competency window test",
+        about: "The Competency window maps how someone's workplace strengths are seen by themselves and by their colleagues",
+        footer: None,
+        window: "Competency window",
+        overall: "The overall competency window",
+        unreadable: "The competency database could not be read",
+        missing_target: "Cannot find user in the competency database",
+        missing_user: "Cannot find the user in the competency database",
+        missing_own: "You are not in the competency database yet",
+        missing_team: "No member with this role is in the competency database",
+        similar: "Similar competency windows",
+        stats: "Competency statistics",
+        team: "Team competency window",
+        team_members: "{} of {} members have a competency window",
+        changes: "Competency window changes",
+        history: "Competency history",
+    };
+}
+
+window_command!(CompetencyCommand, CompetencyAdjectives, "competency", "The competency window test", "Der Kompetenz-Fenster-Test");
+
+pub const STORE: &str = "competency";
+
@@ -0,0 +1,840 @@
+use std::io;
+
+use itertools::Itertools;
+
+use serenity::prelude::*;
+use serenity::{
+    builder::{CreateCommand, CreateEmbed},
+    model::prelude::{CommandInteraction, GuildId, UserId},
+};
+use soshari_macros::SlashCommand;
+
+use crate::audit::{self, Action};
+use crate::locale;
+use crate::sets::{self, AdjectiveSet, Sets, SetsLock, Window};
+use crate::settings;
+use crate::store::{self, Diff, Snapshot};
+use crate::window::{self, Quadrant};
+
+use super::comments;
+use super::picker::{self, Picker, Session};
+use super::util::{
+    autocomplete_option, can_manage_guild, lock, parse_user, respond_autocomplete, respond_embed,
+    respond_embed_components, respond_embed_error, user_choices, Adjective, Visibility,
+};
+
+/// Windows over the adjective sets a guild defines at runtime, next to the built-in
+/// window types.
+#[derive(SlashCommand)]
+#[command(
+    name = "custom",
+    description = "Windows using this server's adjective sets"
+)]
+#[localized(
+    locale = "de",
+    description = "Fenster mit den Adjektivlisten dieses Servers"
+)]
+pub enum CustomCommand {
+    #[command(description = "Start a window test with one of this server's adjective sets")]
+    #[localized(
+        locale = "de",
+        description = "Starte einen Fenster-Test mit einer Adjektivliste dieses Servers"
+    )]
+    Start {
+        #[option(description = "Adjective set to use")]
+        #[localized(locale = "de", description = "Zu verwendende Adjektivliste")]
+        set: String,
+        #[option(description = "User to contribute to", autocomplete = true)]
+        #[localized(locale = "de", description = "Nutzer, zu dessen Fenster du beiträgst")]
+        user: Option<String>,
+    },
+    #[command(description = "Query for a user's window of an adjective set")]
+    #[localized(
+        locale = "de",
+        description = "Frage das Fenster eines Nutzers zu einer Adjektivliste ab"
+    )]
+    Query {
+        #[option(description = "Adjective set to query")]
+        #[localized(locale = "de", description = "Abzufragende Adjektivliste")]
+        set: String,
+        #[option(description = "User to query", autocomplete = true)]
+        #[localized(locale = "de", description = "Abzufragender Nutzer")]
+        user: String,
+        #[option(description = "Only show the window to you, by default as set with privacy")]
+        #[localized(
+            locale = "de",
+            description = "Zeige das Fenster nur dir, standardmäßig wie mit privacy eingestellt"
+        )]
+        private: Option<bool>,
+    },
+}
+
+#[derive(SlashCommand)]
+#[command(name = "sets", description = "Manage this server's adjective sets")]
+#[localized(
+    locale = "de",
+    description = "Verwalte die Adjektivlisten dieses Servers"
+)]
+pub enum SetCommand {
+    #[command(description = "List the adjective sets, or the adjectives of a set")]
+    #[localized(
+        locale = "de",
+        description = "Liste die Adjektivlisten oder die Adjektive einer Liste auf"
+    )]
+    List {
+        #[option(description = "Set whose adjectives to list")]
+        #[localized(
+            locale = "de",
+            description = "Liste, deren Adjektive aufgelistet werden"
+        )]
+        set: Option<String>,
+    },
+    #[command(description = "Create an adjective set")]
+    #[localized(locale = "de", description = "Erstelle eine Adjektivliste")]
+    Create {
+        #[option(description = "Name of the set, e.g. competencies")]
+        #[localized(locale = "de", description = "Name der Liste, z. B. competencies")]
+        name: String,
+        #[option(description = "Comma separated adjective names, e.g. mentoring, public-speaking")]
+        #[localized(
+            locale = "de",
+            description = "Durch Kommas getrennte Adjektive, z. B. mentoring, public-speaking"
+        )]
+        adjectives: String,
+    },
+    #[command(description = "Add an adjective to a set")]
+    #[localized(locale = "de", description = "Füge einer Liste ein Adjektiv hinzu")]
+    Add {
+        #[option(description = "Set to add to")]
+        #[localized(locale = "de", description = "Liste, zu der hinzugefügt wird")]
+        set: String,
+        #[option(description = "Name of the adjective")]
+        #[localized(locale = "de", description = "Name des Adjektivs")]
+        adjective: String,
+    },
+    #[command(description = "Change how an adjective of a set is shown")]
+    #[localized(
+        locale = "de",
+        description = "Ändere, wie ein Adjektiv einer Liste angezeigt wird"
+    )]
+    Edit {
+        #[option(description = "Set of the adjective")]
+        #[localized(locale = "de", description = "Liste des Adjektivs")]
+        set: String,
+        #[option(description = "Name of the adjective")]
+        #[localized(locale = "de", description = "Name des Adjektivs")]
+        adjective: String,
+        #[option(description = "Name shown in menus and windows")]
+        #[localized(locale = "de", description = "In Menüs und Fenstern angezeigter Name")]
+        label: Option<String>,
+        #[option(description = "Description shown in menus")]
+        #[localized(locale = "de", description = "In Menüs angezeigte Beschreibung")]
+        description: Option<String>,
+        #[option(description = "Emoji shown in menus")]
+        #[localized(locale = "de", description = "In Menüs angezeigtes Emoji")]
+        emoji: Option<String>,
+    },
+    #[command(description = "Remove an adjective from a set for good")]
+    #[localized(
+        locale = "de",
+        description = "Entferne ein Adjektiv endgültig aus einer Liste"
+    )]
+    Retire {
+        #[option(description = "Set of the adjective")]
+        #[localized(locale = "de", description = "Liste des Adjektivs")]
+        set: String,
+        #[option(description = "Name of the adjective")]
+        #[localized(locale = "de", description = "Name des Adjektivs")]
+        adjective: String,
+    },
+}
+
+pub fn create() -> CreateCommand {
+    CustomCommand::create()
+}
+
+fn menu_adjectives(set: &AdjectiveSet) -> Vec<Adjective> {
+    set.adjectives
+        .iter()
+        .map(|adjective| Adjective {
+            name: adjective.name.clone(),
+            label: adjective.label().to_string(),
+            description: adjective.description.clone(),
+            emoji: adjective.emoji.clone(),
+        })
+        .collect()
+}
+
+fn set_picker(set: &AdjectiveSet, locale: &str) -> Picker {
+    let adjectives = menu_adjectives(set);
+    let embed = CreateEmbed::new()
+        .title(locale::format(locale, "The {} window test", &[&set.name]))
+        .description(locale::tr(
+            locale,
+            "Pick the adjectives that describe the person best",
+        ))
+        .color((0xFF, 0x5C, 0x5C));
+
+    Picker {
+        embed,
+        min: adjectives.len().min(5),
+        max: adjectives.len(),
+        anonymous: false,
+        adjectives,
+    }
+}
+
+/// The picker of adjective set `name` of guild `guild_id`, shown to `locale`.
+pub fn picker(guild_id: Option<GuildId>, name: &str, locale: &str) -> Result<Picker, &'static str> {
+    let guild_id = guild_id.ok_or("Custom windows can only be used in a server")?;
+    let sets = Sets::load().map_err(|error| {
+        eprintln!("Cannot load the adjective sets: {error}");
+        "The adjective sets could not be read"
+    })?;
+    let set = sets
+        .get(guild_id, name)
+        .ok_or("Cannot find the adjective set")?;
+    Ok(set_picker(set, locale))
+}
+
+/// The comments left with the contributions to `target`'s window of set `name`, `None`
+/// if there is no such window.
+pub fn comments(guild_id: GuildId, name: &str, target: UserId) -> io::Result<Option<Vec<String>>> {
+    let sets = Sets::load()?;
+    Ok(sets
+        .get(guild_id, name)
+        .and_then(|set| set.window(target))
+        .map(Window::comments))
+}
+
+/// Ids of the users who contributed to `target`'s window of set `name`, `None` if there
+/// is no such window.
+pub fn contributions(
+    guild_id: GuildId,
+    name: &str,
+    target: UserId,
+) -> io::Result<Option<Vec<u64>>> {
+    let sets = Sets::load()?;
+    Ok(sets
+        .get(guild_id, name)
+        .and_then(|set| set.window(target))
+        .map(|window| window.others.iter().map(|other| other.id).collect()))
+}
+
+/// Removes every contribution `contributor` made to `target`'s window of set `name`,
+/// returning whether there was any. A snapshot is taken first so the removal can be
+/// restored.
+pub fn remove_contribution(
+    guild_id: GuildId,
+    name: &str,
+    target: UserId,
+    contributor: UserId,
+) -> io::Result<bool> {
+    let mut sets = Sets::load()?;
+    let removed = match sets
+        .get_mut(guild_id, name)
+        .and_then(|set| set.window_mut(target))
+    {
+        Some(window) => {
+            let count = window.others.len();
+            window
+                .others
+                .retain(|other| other.id != u64::from(contributor));
+            window.others.len() != count
+        }
+        None => false,
+    };
+
+    if removed {
+        store::backup(store::path(sets::STORE), true)?;
+        sets.dump()?;
+    }
+    Ok(removed)
+}
+
+/// Removes comment `number` on `target`'s window of set `name`, or all of them,
+/// returning how many were removed. A snapshot is taken first so the comments can be
+/// restored.
+pub fn remove_comments(
+    guild_id: GuildId,
+    name: &str,
+    target: UserId,
+    number: Option<usize>,
+) -> io::Result<usize> {
+    let mut sets = Sets::load()?;
+    let removed = match sets
+        .get_mut(guild_id, name)
+        .and_then(|set| set.window_mut(target))
+    {
+        Some(window) => window.remove_comments(number),
+        None => 0,
+    };
+
+    if removed > 0 {
+        store::backup(store::path(sets::STORE), true)?;
+        sets.dump()?;
+    }
+    Ok(removed)
+}
+
+/// Deletes `target`'s window of set `name` along with every contribution to it,
+/// returning whether there was one. A snapshot is taken first so the window can be
+/// restored.
+pub fn wipe(guild_id: GuildId, name: &str, target: UserId) -> io::Result<bool> {
+    let mut sets = Sets::load()?;
+    let removed = match sets.get_mut(guild_id, name) {
+        Some(set) => {
+            let count = set.windows.len();
+            set.windows.retain(|window| window.id != u64::from(target));
+            set.windows.len() != count
+        }
+        None => false,
+    };
+
+    if removed {
+        store::backup(store::path(sets::STORE), true)?;
+        sets.dump()?;
+    }
+    Ok(removed)
+}
+
+/// Replaces the adjective sets with `snapshot`, returning whose windows changed in any
+/// set. Nothing is written when `dry_run` is set. Sets that cannot be read count as
+/// empty, and are still backed up first.
+pub fn restore(snapshot: &Snapshot, dry_run: bool) -> io::Result<Diff> {
+    let current = Sets::load().unwrap_or_else(|error| {
+        eprintln!("Cannot load the adjective sets, restoring over them: {error}");
+        Sets::default()
+    });
+    let restored = Sets::load_from(&snapshot.path())?;
+
+    let mut diff = Diff::default();
+    let keys = current
+        .all()
+        .iter()
+        .chain(restored.all())
+        .map(|set| (GuildId::new(set.guild_id), set.name.as_str()))
+        .unique();
+    for (guild_id, name) in keys {
+        let windows = |sets| {
+            Sets::get(sets, guild_id, name)
+                .map(|set| set.windows.as_slice())
+                .unwrap_or_default()
+        };
+        diff.extend(Diff::new(windows(&current), windows(&restored)));
+    }
+
+    if !dry_run {
+        store::backup(store::path(sets::STORE), true)?;
+        restored.dump()?;
+    }
+
+    Ok(diff)
+}
+
+/// Records `selected` as the submission of the picker `session`, returning the action
+/// to audit.
+pub fn submit(session: &Session, selected: Vec<String>) -> Result<Action, &'static str> {
+    let guild_id = session
+        .guild_id()
+        .ok_or("Custom windows can only be used in a server")?;
+    // The set may have been edited while the picker was open, so it is read again
+    // rather than overwriting those edits.
+    let mut sets = Sets::load().map_err(|error| {
+        eprintln!("Cannot load the adjective sets: {error}");
+        "The adjective sets could not be read"
+    })?;
+    let set = sets
+        .get_mut(guild_id, &session.window)
+        .ok_or("Cannot find the adjective set")?;
+    let id = session.user_id();
+
+    let action = match session.target() {
+        Some(target_id) => {
+            let target = set
+                .window_mut(target_id)
+                .ok_or("Cannot find the user in the adjective set")?;
+            match target
+                .others
+                .iter_mut()
+                .find(|other| other.id == u64::from(id))
+            {
+                Some(contribution) => contribution.revise(selected, session.comment.clone()),
+                None => {
+                    target
+                        .others
+                        .push(Window::new(id.into(), selected, session.comment.clone()))
+                }
+            }
+            Action::ContributionAdded {
+                window: session.window.clone(),
+                target: target_id.into(),
+            }
+        }
+        None => {
+            match set.window_mut(id) {
+                Some(window) => window.revise(selected, None),
+                None => set.windows.push(Window::new(id.into(), selected, None)),
+            }
+            Action::SelfAssessmentUpdated {
+                window: session.window.clone(),
+            }
+        }
+    };
+
+    store::backup(store::path(sets::STORE), false)
+        .and_then(|()| sets.dump())
+        .map_err(|error| {
+            eprintln!("Cannot save the adjective sets: {error}");
+            "The adjective sets could not be saved"
+        })?;
+    Ok(action)
+}
+
+/// Suggests the members with a window of the set picked with the `set` option, or of
+/// any of the guild's sets before one is picked.
+pub async fn autocomplete(ctx: Context, interaction: CommandInteraction) {
+    let (partial, guild_id) = match (interaction.data.autocomplete(), interaction.guild_id) {
+        (Some(option), Some(guild_id)) if option.name == "user" => (option.value, guild_id),
+        _ => return,
+    };
+    let sets = match Sets::load() {
+        Ok(sets) => sets,
+        Err(error) => {
+            eprintln!("Cannot load the adjective sets: {error}");
+            return;
+        }
+    };
+    let name = autocomplete_option(&interaction, "set");
+    let ids = sets
+        .of(guild_id)
+        .filter(|set| name.is_none_or(|name| set.name == name))
+        .flat_map(|set| set.windows.iter().map(|window| window.id))
+        .unique()
+        .collect::<Vec<_>>();
+    let choices = user_choices(&ctx, Some(guild_id), ids.into_iter(), partial).await;
+    respond_autocomplete(&ctx.http, &interaction, choices).await;
+}
+
+pub async fn run(ctx: Context, command: CommandInteraction) {
+    let guild_id = match command.guild_id {
+        Some(guild_id) => guild_id,
+        None => {
+            respond_embed_error(
+                &ctx.http,
+                command,
+                "Custom windows can only be used in a server",
+                Visibility::Private,
+            )
+            .await;
+            return;
+        }
+    };
+    let sets = match Sets::load() {
+        Ok(sets) => sets,
+        Err(error) => {
+            eprintln!("Cannot load the adjective sets: {error}");
+            respond_embed_error(
+                &ctx.http,
+                command,
+                "The adjective sets could not be read",
+                Visibility::Private,
+            )
+            .await;
+            return;
+        }
+    };
+    let id = command.user.id;
+
+    match CustomCommand::parse(&command) {
+        Some(CustomCommand::Start { set: name, user }) => {
+            let set = match sets.get(guild_id, &name) {
+                Some(set) => set,
+                None => {
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "Cannot find the adjective set",
+                        Visibility::Private,
+                    )
+                    .await;
+                    return;
+                }
+            };
+            let user = match user.map(|user| parse_user(&user)) {
+                Some(Some(user)) => Some(user),
+                Some(None) => {
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "Unknown user, pick one of the suggestions",
+                        Visibility::Private,
+                    )
+                    .await;
+                    return;
+                }
+                None => None,
+            };
+            if let Some(target_id) = user {
+                if target_id == id {
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "You cannot contribute to yourself!",
+                        Visibility::Private,
+                    )
+                    .await;
+                    return;
+                }
+                match settings::is_banned(guild_id, id) {
+                    Ok(false) => {}
+                    Ok(true) => {
+                        respond_embed_error(
+                            &ctx.http,
+                            command,
+                            "You are banned from contributing in this server",
+                            Visibility::Private,
+                        )
+                        .await;
+                        return;
+                    }
+                    Err(error) => {
+                        eprintln!("Cannot load the guild settings: {error}");
+                        respond_embed_error(
+                            &ctx.http,
+                            command,
+                            "The guild settings could not be read",
+                            Visibility::Private,
+                        )
+                        .await;
+                        return;
+                    }
+                }
+                if set.window(target_id).is_none() {
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "Cannot find the user in the adjective set",
+                        Visibility::Private,
+                    )
+                    .await;
+                    return;
+                }
+            }
+
+            let picker = set_picker(set, &command.locale);
+            picker::open(&ctx, &command, &picker, &name, user, Some(guild_id)).await;
+        }
+        Some(CustomCommand::Query {
+            set: name,
+            user,
+            private,
+        }) => {
+            let private = match private {
+                Some(private) => private,
+                None => match settings::is_private(Some(guild_id), id) {
+                    Ok(private) => private,
+                    Err(error) => {
+                        eprintln!("Cannot load the guild settings: {error}");
+                        respond_embed_error(
+                            &ctx.http,
+                            command,
+                            "The guild settings could not be read",
+                            Visibility::Private,
+                        )
+                        .await;
+                        return;
+                    }
+                },
+            };
+            let user = match parse_user(&user) {
+                Some(user) => user,
+                None => {
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "Unknown user, pick one of the suggestions",
+                        Visibility::private(private),
+                    )
+                    .await;
+                    return;
+                }
+            };
+            let set = match sets.get(guild_id, &name) {
+                Some(set) => set,
+                None => {
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "Cannot find the adjective set",
+                        Visibility::private(private),
+                    )
+                    .await;
+                    return;
+                }
+            };
+            let window = match set.window(user) {
+                Some(window) => window,
+                None => {
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "Cannot find the user in the adjective set",
+                        Visibility::private(private),
+                    )
+                    .await;
+                    return;
+                }
+            };
+
+            let others = window
+                .others
+                .iter()
+                .map(|other| set.labels_of(&other.adjectives))
+                .collect::<Vec<_>>();
+            let placements =
+                window::place(&set.labels(), &set.labels_of(&window.adjectives), &others);
+
+            let locale = command.locale.as_str();
+            let embed = CreateEmbed::new()
+                .title(locale::format(locale, "{} window", &[&name]))
+                .description(locale::format(
+                    locale,
+                    "The overall {} window of {}",
+                    &[&name, &format!("<@{user}>")],
+                ))
+                .color((0xFF, 0x5C, 0x5C))
+                .field(
+                    locale::tr(locale, "Arena"),
+                    window::field(&placements, Quadrant::Arena),
+                    true,
+                )
+                .field(
+                    locale::tr(locale, "Blind"),
+                    window::field(&placements, Quadrant::Blind),
+                    true,
+                )
+                .field(
+                    locale::tr(locale, "Facade"),
+                    window::field(&placements, Quadrant::Facade),
+                    true,
+                )
+                .field(
+                    locale::tr(locale, "Unknown"),
+                    window::field(&placements, Quadrant::Unknown),
+                    true,
+                );
+            let components =
+                comments::buttons(locale, Some(guild_id), &name, user, window.comments().len());
+            respond_embed_components(
+                &ctx.http,
+                &command,
+                embed,
+                components,
+                Visibility::private(private),
+            )
+            .await;
+        }
+        None => {
+            respond_embed_error(
+                &ctx.http,
+                command,
+                "The program reached a (supposedly) unreachable state. Something went wrong",
+                Visibility::Private,
+            )
+            .await;
+            panic!("Unreachable state (custom matching error)");
+        }
+    }
+}
+
+/// Runs `/soshari sets`, letting moderators define the guild's adjective sets.
+pub async fn run_sets(ctx: Context, command: CommandInteraction, subcommand: SetCommand) {
+    let guild_id = match command.guild_id {
+        Some(guild_id) => guild_id,
+        None => {
+            respond_embed_error(
+                &ctx.http,
+                command,
+                "Adjective sets can only be managed in a server",
+                Visibility::Private,
+            )
+            .await;
+            return;
+        }
+    };
+    if !can_manage_guild(&command) {
+        respond_embed_error(
+            &ctx.http,
+            command,
+            "You need the Manage Server permission to manage adjective sets",
+            Visibility::Private,
+        )
+        .await;
+        return;
+    }
+    let lock = lock::<SetsLock>(&ctx).await;
+    let guard = lock.lock().await;
+    let mut sets = match Sets::load() {
+        Ok(sets) => sets,
+        Err(error) => {
+            drop(guard);
+            eprintln!("Cannot load the adjective sets: {error}");
+            respond_embed_error(
+                &ctx.http,
+                command,
+                "The adjective sets could not be read",
+                Visibility::Private,
+            )
+            .await;
+            return;
+        }
+    };
+
+    let (set, change) = match subcommand {
+        SetCommand::List { set: None } => {
+            let names = sets
+                .of(guild_id)
+                .map(|set| format!("`{}`", set.name))
+                .collect::<Vec<_>>();
+            let embed = CreateEmbed::new()
+                .title(locale::tr(&command.locale, "Adjective sets"))
+                .description(if names.is_empty() {
+                    locale::tr(&command.locale, "This server has no adjective sets yet").to_string()
+                } else {
+                    names.join("\n")
+                })
+                .color((0xFF, 0x5C, 0x5C));
+            drop(guard);
+            respond_embed(&ctx.http, &command, embed, Visibility::Private).await;
+            return;
+        }
+        SetCommand::List { set: Some(name) } => {
+            let set = match sets.get(guild_id, &name) {
+                Some(set) => set,
+                None => {
+                    drop(guard);
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "Cannot find the adjective set",
+                        Visibility::Private,
+                    )
+                    .await;
+                    return;
+                }
+            };
+            let embed = CreateEmbed::new()
+                .title(locale::format(
+                    &command.locale,
+                    "The {} adjective set",
+                    &[&name],
+                ))
+                .description(
+                    set.adjectives
+                        .iter()
+                        .map(|adjective| {
+                            format!(
+                                "{}`{}` {}{}",
+                                adjective
+                                    .emoji
+                                    .as_ref()
+                                    .map(|emoji| format!("{emoji} "))
+                                    .unwrap_or_default(),
+                                adjective.name,
+                                adjective.label(),
+                                adjective
+                                    .description
+                                    .as_ref()
+                                    .map(|description| format!(": {description}"))
+                                    .unwrap_or_default()
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                )
+                .color((0xFF, 0x5C, 0x5C));
+            drop(guard);
+            respond_embed(&ctx.http, &command, embed, Visibility::Private).await;
+            return;
+        }
+        SetCommand::Create { name, adjectives } => {
+            let adjectives = adjectives
+                .split(',')
+                .map(str::trim)
+                .filter(|adjective| !adjective.is_empty())
+                .collect::<Vec<_>>();
+            let result =
+                AdjectiveSet::new(guild_id, &name, &adjectives).and_then(|set| sets.insert(set));
+            (
+                result.map(|_| name),
+                format!("created with {} adjectives", adjectives.len()),
+            )
+        }
+        SetCommand::Add { set, adjective } => (
+            edit(&mut sets, guild_id, set, |set| set.add(&adjective)),
+            format!("added `{adjective}`"),
+        ),
+        SetCommand::Edit {
+            set,
+            adjective,
+            label,
+            description,
+            emoji,
+        } => (
+            edit(&mut sets, guild_id, set, |set| {
+                set.edit(&adjective, label, description, emoji)
+            }),
+            format!("edited `{adjective}`"),
+        ),
+        SetCommand::Retire { set, adjective } => (
+            edit(&mut sets, guild_id, set, |set| set.retire(&adjective)),
+            format!("retired `{adjective}`"),
+        ),
+    };
+
+    let set = match set {
+        Ok(set) => set,
+        Err(message) => {
+            drop(guard);
+            respond_embed_error(&ctx.http, command, &message, Visibility::Private).await;
+            return;
+        }
+    };
+    let saved = store::backup(store::path(sets::STORE), false).and_then(|()| sets.dump());
+    drop(guard);
+    if let Err(error) = saved {
+        eprintln!("Cannot save the adjective sets: {error}");
+        respond_embed_error(
+            &ctx.http,
+            command,
+            "The adjective sets could not be saved",
+            Visibility::Private,
+        )
+        .await;
+        return;
+    }
+
+    let action = Action::SetUpdated { set, change };
+    let locale = command.locale.as_str();
+    let embed = CreateEmbed::new()
+        .title(locale::tr(locale, action.title()))
+        .description(action.description(locale))
+        .color((0xFF, 0x5C, 0x5C));
+    audit::record(&ctx, Some(guild_id), Some(command.user.id), action).await;
+    respond_embed(&ctx.http, &command, embed, Visibility::Private).await;
+}
+
+/// Applies `change` to the set named `name`, returning the name back on success.
+fn edit(
+    sets: &mut Sets,
+    guild_id: GuildId,
+    name: String,
+    change: impl FnOnce(&mut AdjectiveSet) -> Result<(), String>,
+) -> Result<String, String> {
+    let set = sets
+        .get_mut(guild_id, &name)
+        .ok_or_else(|| format!("Cannot find the adjective set `{name}`"))?;
+    change(set)?;
+    Ok(name)
+}
@@ -0,0 +1,1223 @@
+use itertools::Itertools;
+use std::{io, ops::BitOr, path::Path};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serenity::prelude::*;
+use serenity::{
+    builder::{CreateEmbed, CreateEmbedFooter},
+    model::prelude::{CommandInteraction, GuildId, RoleId, UserId},
+};
+
+use crate::audit::Action;
+use crate::history::{self, Revision};
+use crate::locale;
+use crate::settings::{self, Settings, SettingsLock};
+use crate::stats;
+use crate::store::{self, Diff, Snapshot};
+use crate::window::{self, Placement, Quadrant};
+
+use super::comments;
+use super::picker::{self, Picker, Session};
+use super::util::{
+    autocomplete_guild, can_manage_guild, contribution_guild, guild_choices, guild_members, lock, parse_user, respond_autocomplete,
+    respond_embed, respond_embed_components, respond_embed_error, user_choices, Adjective,
+    Invocation, Visibility,
+};
+
+/// A user's window: their own submission along with every contribution made to it, each
+/// contribution being a window of its own without further contributions.
+#[derive(Serialize, Deserialize)]
+pub struct Window<A> {
+    pub(super) id: u64,
+    pub(super) adjectives: A,
+    /// When `adjectives` were submitted, 0 for submissions made before this was recorded.
+    #[serde(default)]
+    timestamp: u64,
+    /// Earlier submissions, oldest first.
+    #[serde(default)]
+    history: Vec<Revision<A>>,
+    others: Vec<Window<A>>,
+    /// The comment left with a contribution, shown without its author.
+    #[serde(default)]
+    comment: Option<String>,
+}
+
+/// The adjectives `#[adjectives]` generates for a window type, implemented with
+/// [`impl_window`].
+pub trait Adjectives:
+    Copy + Serialize + DeserializeOwned + From<Vec<String>> + BitOr<Output = Self> + Send + Sync
+{
+    type Group: Group<Self>;
+
+    fn all() -> Self;
+    fn empty() -> Self;
+    fn as_labels(&self) -> Vec<&'static str>;
+    /// Every adjective name, in list order.
+    fn adjectives() -> &'static [&'static str];
+    fn label(adjective: &str) -> Option<&'static str>;
+    fn description(adjective: &str) -> Option<&'static str>;
+    fn emoji(adjective: &str) -> Option<&'static str>;
+    fn similarity(&self, other: &Self) -> Option<f64>;
+}
+
+/// The windows of a type as `#[adjectives]` persists them, implemented with
+/// [`impl_window`].
+pub trait Group<A>: Sized + Send + Sync {
+    fn empty() -> Self;
+    fn load(path: &Path) -> io::Result<Self>;
+    fn dump(&self, path: &Path) -> io::Result<()>;
+    fn windows(&self) -> &[Window<A>];
+    fn get(&self, id: u64) -> Option<&Window<A>>;
+    fn get_mut(&mut self, id: u64) -> Option<&mut Window<A>>;
+    fn remove(&mut self, id: u64) -> Option<Window<A>>;
+    fn push(&mut self, window: Window<A>);
+}
+
+/// Implements [`Adjectives`] and [`Group`] for the types `#[adjectives]` generated, by
+/// their inherent methods.
+macro_rules! impl_window {
+    ($adjectives:ident, $group:ident) => {
+        impl $crate::commands::engine::Adjectives for $adjectives {
+            type Group = $group;
+
+            fn all() -> Self {
+                $adjectives::all()
+            }
+
+            fn empty() -> Self {
+                $adjectives::empty()
+            }
+
+            fn as_labels(&self) -> Vec<&'static str> {
+                $adjectives::as_labels(self)
+            }
+
+            fn adjectives() -> &'static [&'static str] {
+                $adjectives::adjectives()
+            }
+
+            fn label(adjective: &str) -> Option<&'static str> {
+                $adjectives::label(adjective)
+            }
+
+            fn description(adjective: &str) -> Option<&'static str> {
+                $adjectives::description(adjective)
+            }
+
+            fn emoji(adjective: &str) -> Option<&'static str> {
+                $adjectives::emoji(adjective)
+            }
+
+            fn similarity(&self, other: &Self) -> Option<f64> {
+                $adjectives::similarity(self, other)
+            }
+        }
+
+        impl $crate::commands::engine::Group<$adjectives> for $group {
+            fn empty() -> Self {
+                $group(Vec::new())
+            }
+
+            fn load(path: &std::path::Path) -> std::io::Result<Self> {
+                $group::load(path)
+            }
+
+            fn dump(&self, path: &std::path::Path) -> std::io::Result<()> {
+                $group::dump(self, path)
+            }
+
+            fn windows(&self) -> &[$crate::commands::engine::Window<$adjectives>] {
+                &self.0
+            }
+
+            fn get(&self, id: u64) -> Option<&$crate::commands::engine::Window<$adjectives>> {
+                $group::get(self, id)
+            }
+
+            fn get_mut(
+                &mut self,
+                id: u64,
+            ) -> Option<&mut $crate::commands::engine::Window<$adjectives>> {
+                $group::get_mut(self, id)
+            }
+
+            fn remove(&mut self, id: u64) -> Option<$crate::commands::engine::Window<$adjectives>> {
+                $group::remove(self, id)
+            }
+
+            fn push(&mut self, window: $crate::commands::engine::Window<$adjectives>) {
+                $group::push(self, window)
+            }
+        }
+    };
+}
+pub(crate) use impl_window;
+
+/// Declares the slash command `$name` of a window type as the enum `$command`, with its
+/// description in English and German, along with the `create`, `autocomplete` and `run`
+/// functions running it on the engine for `$adjectives`.
+macro_rules! window_command {
+    ($command:ident, $adjectives:ident, $name:tt, $description:tt, $de:tt) => {
+        #[derive(soshari_macros::SlashCommand)]
+        #[command(name = $name, description = $description)]
+        #[localized(locale = "de", description = $de)]
+        pub enum $command {
+            #[command(description = "Start the window test")]
+            #[localized(locale = "de", description = "Starte den Fenster-Test")]
+            Start {
+                #[option(description = "User to contribute to", autocomplete = true)]
+                #[localized(locale = "de", description = "Nutzer, zu dessen Fenster du beiträgst")]
+                user: Option<String>,
+                #[option(description = "Server to contribute from when used in DMs", autocomplete = true)]
+                #[localized(locale = "de", description = "Server, von dem aus du in Direktnachrichten beiträgst")]
+                guild: Option<String>,
+            },
+            #[command(description = "Query for a user's window")]
+            #[localized(locale = "de", description = "Frage das Fenster eines Nutzers ab")]
+            Query {
+                #[option(description = "User to query", autocomplete = true)]
+                #[localized(locale = "de", description = "Abzufragender Nutzer")]
+                user: String,
+                #[option(description = "Only show the window to you, by default as set with privacy")]
+                #[localized(locale = "de", description = "Zeige das Fenster nur dir, standardmäßig wie mit privacy eingestellt")]
+                private: Option<bool>,
+            },
+            #[command(description = "Rank the members of this server by how similar their window is to yours")]
+            #[localized(locale = "de", description = "Ordne die Mitglieder dieses Servers nach der Ähnlichkeit ihres Fensters zu deinem")]
+            Similar {
+                #[option(description = "Compare self-assessments (self, the default) or peers' views (peers)")]
+                #[localized(locale = "de", description = "Vergleiche Selbsteinschätzungen (self, Standard) oder Fremdeinschätzungen (peers)")]
+                view: Option<String>,
+                #[option(description = "Only show the result to you, by default as set with privacy")]
+                #[localized(locale = "de", description = "Zeige das Ergebnis nur dir, standardmäßig wie mit privacy eingestellt")]
+                private: Option<bool>,
+            },
+            #[command(description = "Choose whether you appear in other members' similar rankings")]
+            #[localized(locale = "de", description = "Lege fest, ob du in den Ähnlichkeitsranglisten anderer Mitglieder erscheinst")]
+            Discoverable {
+                #[option(description = "Whether you appear in rankings")]
+                #[localized(locale = "de", description = "Ob du in Ranglisten erscheinst")]
+                visible: bool,
+            },
+            #[command(description = "Choose whether your queries are only shown to you by default")]
+            #[localized(locale = "de", description = "Lege fest, ob deine Abfragen standardmäßig nur dir angezeigt werden")]
+            Privacy {
+                #[option(description = "Whether queries are only shown to you")]
+                #[localized(locale = "de", description = "Ob Abfragen nur dir angezeigt werden")]
+                private: bool,
+            },
+            #[command(description = "Show the window statistics of this server")]
+            #[localized(locale = "de", description = "Zeige die Fenster-Statistiken dieses Servers")]
+            Stats {
+                #[option(description = "Only show the result to you, by default as set with privacy")]
+                #[localized(locale = "de", description = "Zeige das Ergebnis nur dir, standardmäßig wie mit privacy eingestellt")]
+                private: Option<bool>,
+            },
+            #[command(description = "Show the combined window of every member with a role")]
+            #[localized(locale = "de", description = "Zeige das gemeinsame Fenster aller Mitglieder mit einer Rolle")]
+            Team {
+                #[option(description = "Role whose members make up the team")]
+                #[localized(locale = "de", description = "Rolle, deren Mitglieder das Team bilden")]
+                role: serenity::model::prelude::RoleId,
+                #[option(description = "Only show the result to you, by default as set with privacy")]
+                #[localized(locale = "de", description = "Zeige das Ergebnis nur dir, standardmäßig wie mit privacy eingestellt")]
+                private: Option<bool>,
+            },
+            #[command(description = "Show which adjectives moved between quadrants since a point in time")]
+            #[localized(locale = "de", description = "Zeige, welche Adjektive seit einem Zeitpunkt den Quadranten gewechselt haben")]
+            Diff {
+                #[option(description = "A date like 2024-01-31, or a duration like 30d, 2w or 12h")]
+                #[localized(locale = "de", description = "Ein Datum wie 2024-01-31 oder eine Dauer wie 30d, 2w oder 12h")]
+                since: String,
+                #[option(description = "User whose window to compare, yourself by default")]
+                #[localized(locale = "de", description = "Nutzer, dessen Fenster verglichen wird, standardmäßig du selbst")]
+                user: Option<serenity::model::prelude::UserId>,
+                #[option(description = "Only show the result to you, by default as set with privacy")]
+                #[localized(locale = "de", description = "Zeige das Ergebnis nur dir, standardmäßig wie mit privacy eingestellt")]
+                private: Option<bool>,
+            },
+            #[command(description = "Show how a user's window changed over time")]
+            #[localized(locale = "de", description = "Zeige, wie sich das Fenster eines Nutzers im Lauf der Zeit verändert hat")]
+            History {
+                #[option(description = "User whose history to show, yourself by default")]
+                #[localized(locale = "de", description = "Nutzer, dessen Verlauf angezeigt wird, standardmäßig du selbst")]
+                user: Option<serenity::model::prelude::UserId>,
+                #[option(description = "Only show the result to you, by default as set with privacy")]
+                #[localized(locale = "de", description = "Zeige das Ergebnis nur dir, standardmäßig wie mit privacy eingestellt")]
+                private: Option<bool>,
+            },
+        }
+
+        impl TryFrom<$command> for $crate::commands::engine::Subcommand {
+            type Error = &'static str;
+
+            fn try_from(command: $command) -> Result<Self, Self::Error> {
+                Ok(match command {
+                    $command::Start { user, guild } => Self::start(user, guild)?,
+                    $command::Query { user, private } => Self::query(user, private)?,
+                    $command::Similar { view, private } => Self::Similar { view, private },
+                    $command::Discoverable { visible } => Self::Discoverable { visible },
+                    $command::Privacy { private } => Self::Privacy { private },
+                    $command::Stats { private } => Self::Stats { private },
+                    $command::Team { role, private } => Self::Team { role, private },
+                    $command::Diff { since, user, private } => Self::Diff { since, user, private },
+                    $command::History { user, private } => Self::History { user, private },
+                })
+            }
+        }
+
+        pub fn create() -> serenity::builder::CreateCommand {
+            $command::create()
+        }
+
+        pub async fn autocomplete(
+            ctx: serenity::prelude::Context,
+            interaction: serenity::model::prelude::CommandInteraction,
+        ) {
+            $crate::commands::engine::autocomplete::<$adjectives>(ctx, interaction).await;
+        }
+
+        pub async fn run(ctx: serenity::prelude::Context, command: serenity::model::prelude::CommandInteraction) {
+            let subcommand = $command::parse(&command).map($crate::commands::engine::Subcommand::try_from);
+            $crate::commands::engine::run::<$adjectives>(ctx, command, subcommand).await;
+        }
+    };
+}
+pub(crate) use window_command;
+
+/// The texts naming a window type, each with an entry in the locale catalogs.
+pub struct Texts {
+    /// Title of the picker, e.g. `The Johari window test`.
+    pub test: &'static str,
+    /// Description of the picker.
+    pub about: &'static str,
+    /// Footer of the picker, if any.
+    pub footer: Option<&'static str>,
+    /// Title of a queried window, e.g. `Johari window`.
+    pub window: &'static str,
+    pub overall: &'static str,
+    pub unreadable: &'static str,
+    /// Error contributing to a user without a window.
+    pub missing_target: &'static str,
+    /// Error querying a user without a window.
+    pub missing_user: &'static str,
+    /// Error ranking without a window of one's own.
+    pub missing_own: &'static str,
+    pub missing_team: &'static str,
+    pub similar: &'static str,
+    pub stats: &'static str,
+    pub team: &'static str,
+    /// Follows `<role>:` in team windows, with `{}` for the members with a window and
+    /// the members of the role.
+    pub team_members: &'static str,
+    pub changes: &'static str,
+    pub history: &'static str,
+}
+
+/// A built-in window type: its adjectives along with how it is stored and shown.
+pub trait Kind: Adjectives + 'static {
+    const STORE: &'static str;
+    const COLOR: (u8, u8, u8);
+    /// How many adjectives a submission takes at least.
+    const MIN: usize;
+    /// How many adjectives a submission takes at most, every one by default.
+    const MAX: Option<usize> = None;
+    /// Whether contributions are anonymous, so that nothing shown may tell them apart.
+    const ANONYMOUS: bool = false;
+    const TEXTS: Texts;
+}
+
+/// The subcommands every window type offers, parsed from the command of the type.
+pub enum Subcommand {
+    Start {
+        user: Option<UserId>,
+        guild: Option<String>,
+    },
+    Query {
+        user: UserId,
+        private: Option<bool>,
+    },
+    Similar {
+        view: Option<String>,
+        private: Option<bool>,
+    },
+    Discoverable {
+        visible: bool,
+    },
+    Privacy {
+        private: bool,
+    },
+    Stats {
+        private: Option<bool>,
+    },
+    Team {
+        role: RoleId,
+        private: Option<bool>,
+    },
+    Diff {
+        since: String,
+        user: Option<UserId>,
+        private: Option<bool>,
+    },
+    History {
+        user: Option<UserId>,
+        private: Option<bool>,
+    },
+}
+
+impl Subcommand {
+    /// `start` with the autocompleted `user` option, which holds a user id unless
+    /// something other than a suggestion was entered.
+    pub fn start(user: Option<String>, guild: Option<String>) -> Result<Self, &'static str> {
+        let user = match user {
+            Some(user) => Some(parse_user(&user).ok_or("Unknown user, pick one of the suggestions")?),
+            None => None,
+        };
+        Ok(Self::Start { user, guild })
+    }
+
+    /// `query` with the autocompleted `user` option, see [`Subcommand::start`].
+    pub fn query(user: String, private: Option<bool>) -> Result<Self, &'static str> {
+        let user = parse_user(&user).ok_or("Unknown user, pick one of the suggestions")?;
+        Ok(Self::Query { user, private })
+    }
+
+    /// How the response is shown: as asked with the `private` option, or else as the
+    /// user set with `privacy`. Starting a test and changing settings only concern the
+    /// user, so they are always private.
+    fn visibility(&self, guild_id: Option<GuildId>, user_id: UserId) -> io::Result<Visibility> {
+        let private = match self {
+            Self::Query { private, .. }
+            | Self::Similar { private, .. }
+            | Self::Stats { private }
+            | Self::Team { private, .. }
+            | Self::Diff { private, .. }
+            | Self::History { private, .. } => *private,
+            Self::Start { .. } | Self::Discoverable { .. } | Self::Privacy { .. } => Some(true),
+        };
+        match private {
+            Some(private) => Ok(Visibility::private(private)),
+            None => settings::is_private(guild_id, user_id).map(Visibility::private),
+        }
+    }
+}
+
+impl<A: Adjectives> Window<A> {
+    fn new(id: u64, adjectives: A, comment: Option<String>) -> Self {
+        Self {
+            id,
+            adjectives,
+            timestamp: store::now(),
+            history: Vec::new(),
+            others: Vec::new(),
+            comment,
+        }
+    }
+
+    /// Replaces the submitted adjectives, keeping the previous submission in `history`.
+    /// The comment is only replaced when a new one is left.
+    fn revise(&mut self, adjectives: A, comment: Option<String>) {
+        if comment.is_some() {
+            self.comment = comment;
+        }
+        self.history.push(Revision {
+            timestamp: self.timestamp,
+            adjectives: self.adjectives,
+        });
+        self.adjectives = adjectives;
+        self.timestamp = store::now();
+    }
+
+    /// The comments left with the contributions to this window.
+    fn comments(&self) -> Vec<String> {
+        self.others
+            .iter()
+            .filter_map(|other| other.comment.clone())
+            .collect()
+    }
+
+    /// Clears comment `number` of [`Window::comments`], counting from 1, or every
+    /// comment without a `number`, returning how many were cleared.
+    fn remove_comments(&mut self, number: Option<usize>) -> usize {
+        self.others
+            .iter_mut()
+            .filter(|other| other.comment.is_some())
+            .enumerate()
+            .filter(|(index, _)| number.is_none_or(|number| number == index + 1))
+            .map(|(_, other)| other.comment = None)
+            .count()
+    }
+
+    /// The adjectives submitted as of `timestamp`, `None` if there was no submission yet.
+    fn at(&self, timestamp: u64) -> Option<A> {
+        self.history
+            .iter()
+            .map(|revision| (revision.timestamp, revision.adjectives))
+            .chain(std::iter::once((self.timestamp, self.adjectives)))
+            .take_while(|(submitted, _)| *submitted <= timestamp)
+            .last()
+            .map(|(_, adjectives)| adjectives)
+    }
+
+    /// Where each adjective fell in this window as of `timestamp`.
+    fn placements(&self, timestamp: u64) -> Vec<Placement<'static>> {
+        window::place(
+            &A::all().as_labels(),
+            &self.at(timestamp).map(|adjectives| adjectives.as_labels()).unwrap_or_default(),
+            &self
+                .others
+                .iter()
+                .filter_map(|other| other.at(timestamp))
+                .map(|adjectives| adjectives.as_labels())
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Every submission as its timestamp and labels, oldest first.
+    fn submissions(&self) -> Vec<(u64, Vec<&'static str>)> {
+        self.history
+            .iter()
+            .map(|revision| (revision.timestamp, revision.adjectives.as_labels()))
+            .chain(std::iter::once((self.timestamp, self.adjectives.as_labels())))
+            .collect()
+    }
+}
+
+fn load<K: Kind>() -> io::Result<K::Group> {
+    K::Group::load(&store::path(K::STORE))
+}
+
+/// Replaces the database of `K` with `snapshot`, returning what changed. Nothing is
+/// written when `dry_run` is set. A database that cannot be read counts as empty, so a
+/// snapshot can replace it; it is still backed up first.
+pub fn restore<K: Kind>(snapshot: &Snapshot, dry_run: bool) -> io::Result<Diff> {
+    let current = load::<K>().unwrap_or_else(|error| {
+        eprintln!("Cannot load the {} database, restoring over it: {error}", K::STORE);
+        K::Group::empty()
+    });
+    let restored = K::Group::load(&snapshot.path())?;
+    let diff = Diff::new(current.windows(), restored.windows());
+
+    if !dry_run {
+        store::backup(store::path(K::STORE), true)?;
+        restored.dump(&store::path(K::STORE))?;
+    }
+
+    Ok(diff)
+}
+
+/// Ids of the users who contributed to `target`'s window, `None` if they have no window.
+pub fn contributions<K: Kind>(target: UserId) -> io::Result<Option<Vec<u64>>> {
+    let group = load::<K>()?;
+    Ok(group
+        .get(target.into())
+        .map(|window| window.others.iter().map(|other| other.id).collect()))
+}
+
+/// The comments left with the contributions to `target`'s window, `None` if they have
+/// no window.
+pub fn comments<K: Kind>(target: UserId) -> io::Result<Option<Vec<String>>> {
+    let group = load::<K>()?;
+    Ok(group.get(target.into()).map(Window::comments))
+}
+
+/// Removes comment `number` on `target`'s window, or all of them, returning how many
+/// were removed. A snapshot is taken first so the comments can be restored.
+pub fn remove_comments<K: Kind>(target: UserId, number: Option<usize>) -> io::Result<usize> {
+    let mut group = load::<K>()?;
+    let removed = match group.get_mut(target.into()) {
+        Some(window) => window.remove_comments(number),
+        None => 0,
+    };
+
+    if removed > 0 {
+        store::backup(store::path(K::STORE), true)?;
+        group.dump(&store::path(K::STORE))?;
+    }
+    Ok(removed)
+}
+
+/// Removes every contribution `contributor` made to `target`'s window, returning whether
+/// there was any. A snapshot is taken first so the removal can be restored.
+pub fn remove_contribution<K: Kind>(target: UserId, contributor: UserId) -> io::Result<bool> {
+    let mut group = load::<K>()?;
+    let removed = match group.get_mut(target.into()) {
+        Some(window) => {
+            let count = window.others.len();
+            window.others.retain(|other| other.id != u64::from(contributor));
+            window.others.len() != count
+        }
+        None => false,
+    };
+
+    if removed {
+        store::backup(store::path(K::STORE), true)?;
+        group.dump(&store::path(K::STORE))?;
+    }
+    Ok(removed)
+}
+
+/// Deletes `target`'s window along with every contribution to it, returning whether
+/// there was one. A snapshot is taken first so the window can be restored.
+pub fn wipe<K: Kind>(target: UserId) -> io::Result<bool> {
+    let mut group = load::<K>()?;
+    let removed = group.remove(target.into()).is_some();
+
+    if removed {
+        store::backup(store::path(K::STORE), true)?;
+        group.dump(&store::path(K::STORE))?;
+    }
+    Ok(removed)
+}
+
+/// The picker of `K`, shown to `locale`.
+pub fn picker<K: Kind>(locale: &str) -> Picker {
+    let mut embed = CreateEmbed::new()
+        .title(locale::tr(locale, K::TEXTS.test))
+        .description(locale::tr(locale, K::TEXTS.about))
+        .color(K::COLOR);
+    if let Some(footer) = K::TEXTS.footer {
+        embed = embed.footer(CreateEmbedFooter::new(locale::tr(locale, footer)));
+    }
+    let adjectives = K::adjectives()
+        .iter()
+        .map(|adjective| Adjective {
+            name: adjective.to_string(),
+            label: K::label(adjective).unwrap().to_string(),
+            description: K::description(adjective).map(ToString::to_string),
+            emoji: K::emoji(adjective).map(ToString::to_string),
+        })
+        .collect::<Vec<_>>();
+
+    Picker {
+        embed,
+        min: K::MIN,
+        max: K::MAX.unwrap_or(adjectives.len()),
+        anonymous: K::ANONYMOUS,
+        adjectives,
+    }
+}
+
+/// Records `selected` as the submission of the picker `session`, returning the action
+/// to audit.
+pub fn submit<K: Kind>(session: &Session, selected: Vec<String>) -> Result<Action, &'static str> {
+    let mut group = load::<K>().map_err(|error| {
+        eprintln!("Cannot load the {} database: {error}", K::STORE);
+        K::TEXTS.unreadable
+    })?;
+    let id = u64::from(session.user_id());
+
+    let submitted = K::from(selected);
+    let action = if let Some(target_id) = session.target() {
+        let target = group
+            .get_mut(target_id.into())
+            .ok_or(K::TEXTS.missing_target)?;
+        match target.others.iter_mut().find(|other| other.id == id) {
+            Some(contribution) => contribution.revise(submitted, session.comment.clone()),
+            None => target.others.push(Window::new(id, submitted, session.comment.clone())),
+        }
+        Action::ContributionAdded {
+            window: K::STORE.to_string(),
+            target: target_id.into(),
+        }
+    } else {
+        match group.get_mut(id) {
+            Some(window) => window.revise(submitted, None),
+            None => group.push(Window::new(id, submitted, None)),
+        }
+        Action::SelfAssessmentUpdated {
+            window: K::STORE.to_string(),
+        }
+    };
+
+    store::backup(store::path(K::STORE), false)
+        .and_then(|()| group.dump(&store::path(K::STORE)))
+        .map_err(|error| {
+            eprintln!("Cannot save the {} database: {error}", K::STORE);
+            "The window database could not be updated"
+        })?;
+    Ok(action)
+}
+
+pub async fn autocomplete<K: Kind>(ctx: Context, interaction: CommandInteraction) {
+    let choices = match interaction.data.autocomplete() {
+        Some(option) if option.name == "guild" => {
+            guild_choices(&ctx, interaction.user.id, option.value).await
+        }
+        Some(option) if option.name == "user" => {
+            let group = match load::<K>() {
+                Ok(group) => group,
+                Err(error) => {
+                    eprintln!("Cannot load the {} database: {error}", K::STORE);
+                    return;
+                }
+            };
+            let ids = group.windows().iter().map(|window| window.id);
+            let guild_id = autocomplete_guild(&ctx, &interaction).await;
+            user_choices(&ctx, guild_id, ids, option.value).await
+        }
+        _ => return,
+    };
+    respond_autocomplete(&ctx.http, &interaction, choices).await;
+}
+
+/// Runs the slash command of `K`, whose options were checked when turned into `subcommand`.
+pub async fn run<K: Kind>(ctx: Context, command: CommandInteraction, subcommand: Option<Result<Subcommand, &'static str>>) {
+    match subcommand.transpose() {
+        Ok(subcommand) => execute::<K>(ctx, command, subcommand).await,
+        Err(message) => respond_embed_error(&ctx.http, command, message, Visibility::Private).await,
+    }
+}
+
+/// Runs `subcommand` of `K` for `command`, which is a slash or user command, or a panel
+/// button standing in for one.
+pub async fn execute<K: Kind>(ctx: Context, command: impl Invocation, subcommand: Option<Subcommand>) {
+    let id = command.user().id;
+    let visibility = match &subcommand {
+        Some(subcommand) => subcommand.visibility(command.guild_id(), id),
+        None => Ok(Visibility::Private),
+    };
+    let visibility = match visibility {
+        Ok(visibility) => visibility,
+        Err(error) => {
+            eprintln!("Cannot load the guild settings: {error}");
+            respond_embed_error(&ctx.http, command, "The guild settings could not be read", Visibility::Private).await;
+            return;
+        }
+    };
+    let locale = command.locale();
+    let group = match load::<K>() {
+        Ok(group) => group,
+        Err(error) => {
+            eprintln!("Cannot load the {} database: {error}", K::STORE);
+            respond_embed_error(&ctx.http, command, K::TEXTS.unreadable, visibility).await;
+            return;
+        }
+    };
+    match subcommand {
+        Some(Subcommand::Start { user, guild }) => {
+            let guild_id = match contribution_guild(&ctx, &command, guild).await {
+                Ok(guild_id) => guild_id,
+                Err(message) => {
+                    respond_embed_error(&ctx.http, command, message, visibility).await;
+                    return;
+                }
+            };
+            if let Some(target_id) = user {
+                if target_id == id {
+                    respond_embed_error(&ctx.http, command, "You cannot contribute to yourself!", visibility)
+                        .await;
+                    return;
+                }
+                // Bans are per guild, so a contribution always comes from one.
+                let Some(guild_id) = guild_id else {
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "Pick the server to contribute from with the guild option",
+                        visibility,
+                    )
+                    .await;
+                    return;
+                };
+                match settings::is_banned(guild_id, id) {
+                    Ok(false) => {}
+                    Ok(true) => {
+                        respond_embed_error(
+                            &ctx.http,
+                            command,
+                            "You are banned from contributing in this server",
+                            visibility,
+                        )
+                        .await;
+                        return;
+                    }
+                    Err(error) => {
+                        eprintln!("Cannot load the guild settings: {error}");
+                        respond_embed_error(&ctx.http, command, "The guild settings could not be read", visibility).await;
+                        return;
+                    }
+                }
+                if group.get(target_id.into()).is_none() {
+                    respond_embed_error(&ctx.http, command, K::TEXTS.missing_target, visibility).await;
+                    return;
+                }
+            }
+
+            picker::open(&ctx, &command, &picker::<K>(command.locale()), K::STORE, user, guild_id).await;
+        }
+        Some(Subcommand::Query { user: target_id, .. }) => {
+            if let Some(target) = group.get(target_id.into()) {
+                // The target may have left the guild or never been in it, their window
+                // then keeps the default color.
+                let roles = match command.guild_id() {
+                    Some(guild_id) => guild_id
+                        .member(&ctx.http, target_id)
+                        .await
+                        .ok()
+                        .and_then(|member| member.roles(&ctx.cache)),
+                    None => None,
+                };
+                let color = match roles {
+                    Some(role) => role
+                        .last()
+                        .map(|role| role.colour)
+                        .unwrap_or((0, 0, 0).into()),
+                    None => (0, 0, 0).into(),
+                };
+
+                let own = target.adjectives.as_labels();
+                let others = target
+                    .others
+                    .iter()
+                    .map(|other| other.adjectives.as_labels())
+                    .collect::<Vec<_>>();
+                let placements = window::place(&K::all().as_labels(), &own, &others);
+                let agreement = window::agreement(&own, &others);
+                let placements = placements
+                    .iter()
+                    .map(|placement| Placement {
+                        adjective: locale::adjective(locale, placement.adjective),
+                        ..*placement
+                    })
+                    .collect::<Vec<_>>();
+                let percent = |value: Option<f64>| match value {
+                    Some(value) => format!("{:.0}%", value * 100.0),
+                    None => locale::tr(locale, "N/A").to_string(),
+                };
+                let embed = CreateEmbed::new()
+                    .title(locale::tr(locale, K::TEXTS.window))
+                    .description(locale::tr(locale, K::TEXTS.overall))
+                    .color(color)
+                    .field(locale::tr(locale, "Arena"), window::field(&placements, Quadrant::Arena), true)
+                    .field(locale::tr(locale, "Blind"), window::field(&placements, Quadrant::Blind), true)
+                    .field(locale::tr(locale, "Facade"), window::field(&placements, Quadrant::Facade), true)
+                    .field(locale::tr(locale, "Unknown"), window::field(&placements, Quadrant::Unknown), true)
+                    .field(
+                        locale::tr(locale, "Self-awareness"),
+                        format!(
+                            "{} {}\n{} {}{}",
+                            percent(agreement.overall),
+                            locale::tr(locale, "overlap with the peers' combined view"),
+                            percent(agreement.mean()),
+                            locale::tr(locale, "average agreement with each peer"),
+                            match agreement.peers.iter().flatten().minmax().into_option() {
+                                Some((min, max)) => format!(
+                                    " ({} – {})",
+                                    percent(Some(*min)),
+                                    percent(Some(*max))
+                                ),
+                                None => String::new(),
+                            }
+                        ),
+                        false,
+                    );
+                let components = comments::buttons(locale, command.guild_id(), K::STORE, target_id, target.comments().len());
+                respond_embed_components(&ctx.http, &command, embed, components, visibility).await;
+            } else {
+                respond_embed_error(&ctx.http, command, K::TEXTS.missing_user, visibility).await;
+            }
+        }
+        Some(Subcommand::Similar { view, .. }) => {
+            let guild_id = match command.guild_id() {
+                Some(guild_id) => guild_id,
+                None => {
+                    respond_embed_error(&ctx.http, command, "Rankings can only be shown in a server", visibility).await;
+                    return;
+                }
+            };
+            let peers_view = match view.as_deref() {
+                None | Some("self") => false,
+                Some("peers") => true,
+                Some(_) => {
+                    respond_embed_error(&ctx.http, command, "Unknown view, expected self or peers", visibility).await;
+                    return;
+                }
+            };
+            let view_of = |window: &Window<K>| {
+                if peers_view {
+                    window
+                        .others
+                        .iter()
+                        .fold(K::empty(), |view, other| view | other.adjectives)
+                } else {
+                    window.adjectives
+                }
+            };
+
+            let own = match group.get(id.into()) {
+                Some(window) => view_of(window),
+                None => {
+                    respond_embed_error(&ctx.http, command, K::TEXTS.missing_own, visibility).await;
+                    return;
+                }
+            };
+            let undiscoverable = match Settings::load() {
+                Ok(settings) => settings
+                    .guild(guild_id)
+                    .map(|guild| guild.undiscoverable.clone())
+                    .unwrap_or_default(),
+                Err(error) => {
+                    eprintln!("Cannot load the guild settings: {error}");
+                    respond_embed_error(&ctx.http, command, "The guild settings could not be read", visibility).await;
+                    return;
+                }
+            };
+            let members = match guild_members(&ctx, guild_id).await {
+                Ok(members) => members,
+                Err(error) => {
+                    eprintln!("Cannot list the members of {guild_id}: {error}");
+                    respond_embed_error(&ctx.http, command, "Cannot list the members of this server", visibility).await;
+                    return;
+                }
+            };
+
+            let mut ranking = members
+                .iter()
+                .map(|member| member.user.id)
+                .filter(|member| *member != id && !undiscoverable.contains(&u64::from(*member)))
+                .filter_map(|member| {
+                    let similarity = own.similarity(&view_of(group.get(member.into())?))?;
+                    Some((member, similarity))
+                })
+                .collect::<Vec<_>>();
+            ranking.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+            let embed = CreateEmbed::new()
+                .title(locale::tr(locale, K::TEXTS.similar))
+                .description(if ranking.is_empty() {
+                    locale::tr(locale, "Nobody in this server has a comparable window yet").to_string()
+                } else {
+                    ranking
+                        .iter()
+                        .take(10)
+                        .map(|(member, similarity)| format!("<@{member}> {:.0}%", similarity * 100.0))
+                        .join("\n")
+                })
+                .color(K::COLOR);
+            respond_embed(&ctx.http, &command, embed, visibility).await;
+        }
+        Some(Subcommand::Discoverable { visible }) => {
+            let guild_id = match command.guild_id() {
+                Some(guild_id) => guild_id,
+                None => {
+                    respond_embed_error(&ctx.http, command, "Rankings are only shown in a server", visibility).await;
+                    return;
+                }
+            };
+            let lock = lock::<SettingsLock>(&ctx).await;
+            let guard = lock.lock().await;
+            let mut settings = match Settings::load() {
+                Ok(settings) => settings,
+                Err(error) => {
+                    drop(guard);
+                    eprintln!("Cannot load the guild settings: {error}");
+                    respond_embed_error(&ctx.http, command, "The guild settings could not be read", visibility).await;
+                    return;
+                }
+            };
+            let undiscoverable = &mut settings.guild_mut(guild_id).undiscoverable;
+            undiscoverable.retain(|user| *user != u64::from(id));
+            if !visible {
+                undiscoverable.push(id.into());
+            }
+            let saved = settings.dump();
+            drop(guard);
+            if let Err(error) = saved {
+                eprintln!("Cannot save the guild settings: {error}");
+                respond_embed_error(&ctx.http, command, "The guild settings could not be saved", visibility).await;
+                return;
+            }
+
+            let embed = CreateEmbed::new()
+                .title(locale::tr(locale, "Discoverability updated"))
+                .description(locale::tr(
+                    locale,
+                    if visible {
+                        "You appear in the similar rankings of this server"
+                    } else {
+                        "You no longer appear in the similar rankings of this server"
+                    },
+                ))
+                .color(K::COLOR);
+            respond_embed(&ctx.http, &command, embed, visibility).await;
+        }
+        Some(Subcommand::Privacy { private }) => {
+            let guild_id = match command.guild_id() {
+                Some(guild_id) => guild_id,
+                None => {
+                    respond_embed_error(&ctx.http, command, "Privacy can only be set in a server", visibility).await;
+                    return;
+                }
+            };
+            let lock = lock::<SettingsLock>(&ctx).await;
+            let guard = lock.lock().await;
+            let mut settings = match Settings::load() {
+                Ok(settings) => settings,
+                Err(error) => {
+                    drop(guard);
+                    eprintln!("Cannot load the guild settings: {error}");
+                    respond_embed_error(&ctx.http, command, "The guild settings could not be read", visibility).await;
+                    return;
+                }
+            };
+            let private_queries = &mut settings.guild_mut(guild_id).private_queries;
+            private_queries.retain(|user| *user != u64::from(id));
+            if private {
+                private_queries.push(id.into());
+            }
+            let saved = settings.dump();
+            drop(guard);
+            if let Err(error) = saved {
+                eprintln!("Cannot save the guild settings: {error}");
+                respond_embed_error(&ctx.http, command, "The guild settings could not be saved", visibility).await;
+                return;
+            }
+
+            let embed = CreateEmbed::new()
+                .title(locale::tr(locale, "Privacy updated"))
+                .description(locale::tr(
+                    locale,
+                    if private {
+                        "Your queries in this server are only shown to you unless you ask otherwise"
+                    } else {
+                        "Your queries in this server are shown to the channel unless you ask otherwise"
+                    },
+                ))
+                .color(K::COLOR);
+            respond_embed(&ctx.http, &command, embed, visibility).await;
+        }
+        Some(Subcommand::Stats { .. }) => {
+            let guild_id = match command.guild_id() {
+                Some(guild_id) => guild_id,
+                None => {
+                    respond_embed_error(&ctx.http, command, "Statistics can only be shown in a server", visibility).await;
+                    return;
+                }
+            };
+            let members = match guild_members(&ctx, guild_id).await {
+                Ok(members) => members,
+                Err(error) => {
+                    eprintln!("Cannot list the members of {guild_id}: {error}");
+                    respond_embed_error(&ctx.http, command, "Cannot list the members of this server", visibility).await;
+                    return;
+                }
+            };
+
+            let windows = members
+                .iter()
+                .filter_map(|member| group.get(member.user.id.into()))
+                .map(|window| stats::Window {
+                    own: window.adjectives.as_labels(),
+                    others: window
+                        .others
+                        .iter()
+                        .map(|other| (other.id, other.adjectives.as_labels()))
+                        .collect(),
+                })
+                .collect::<Vec<_>>();
+            let stats = stats::aggregate(&K::all().as_labels(), &windows);
+
+            let mut embed = CreateEmbed::new()
+                .title(locale::tr(locale, K::TEXTS.stats))
+                .description(locale::format(
+                    locale,
+                    "{} windows with {} peer contributions",
+                    &[&stats.windows, &stats.contributions],
+                ))
+                .color(K::COLOR)
+                .field(locale::tr(locale, "Most chosen by users"), stats::field(stats.own_counts.iter()), true)
+                .field(locale::tr(locale, "Least chosen by users"), stats::field(stats.own_counts.iter().rev()), true)
+                .field("\u{200b}", "\u{200b}", false)
+                .field(locale::tr(locale, "Most chosen by peers"), stats::field(stats.peer_counts.iter()), true)
+                .field(locale::tr(locale, "Least chosen by peers"), stats::field(stats.peer_counts.iter().rev()), true)
+                .field(
+                    locale::tr(locale, "Self/peer agreement"),
+                    match stats.agreement {
+                        Some(agreement) => locale::format(
+                            locale,
+                            "{}% of peer choices match the self-assessment",
+                            &[&format!("{:.0}", agreement * 100.0)],
+                        ),
+                        None => locale::tr(locale, "N/A").to_string(),
+                    },
+                    false,
+                );
+            // Naming the contributors to anonymous windows would undo their anonymity.
+            if !K::ANONYMOUS {
+                let contributors = stats
+                    .contributors
+                    .iter()
+                    .take(5)
+                    .map(|(contributor, count)| format!("<@{contributor}> ({count})"))
+                    .join("\n");
+                embed = embed.field(
+                    locale::tr(locale, "Top contributors"),
+                    if contributors.is_empty() { locale::tr(locale, "N/A").to_string() } else { contributors },
+                    false,
+                );
+            }
+            respond_embed(&ctx.http, &command, embed, visibility).await;
+        }
+        Some(Subcommand::Team { role, .. }) => {
+            let guild_id = match command.guild_id() {
+                Some(guild_id) => guild_id,
+                None => {
+                    respond_embed_error(&ctx.http, command, "Team windows can only be shown in a server", visibility).await;
+                    return;
+                }
+            };
+
+            let members = match guild_members(&ctx, guild_id).await {
+                Ok(members) => members
+                    .into_iter()
+                    .filter(|member| member.roles.contains(&role))
+                    .map(|member| member.user.id)
+                    .collect::<Vec<_>>(),
+                Err(error) => {
+                    eprintln!("Cannot list the members of {guild_id}: {error}");
+                    respond_embed_error(&ctx.http, command, "Cannot list the members of this server", visibility).await;
+                    return;
+                }
+            };
+
+            // The team's own view is every self-assessment of its members, and peers'
+            // view every contribution made to any of them.
+            let windows = members
+                .iter()
+                .filter_map(|member| group.get((*member).into()))
+                .collect::<Vec<_>>();
+            if windows.is_empty() {
+                respond_embed_error(&ctx.http, command, K::TEXTS.missing_team, visibility).await;
+                return;
+            }
+            let own = windows
+                .iter()
+                .flat_map(|window| window.adjectives.as_labels())
+                .unique()
+                .collect::<Vec<_>>();
+            let others = windows
+                .iter()
+                .flat_map(|window| &window.others)
+                .map(|other| other.adjectives.as_labels())
+                .collect::<Vec<_>>();
+            let placements = window::place(&K::all().as_labels(), &own, &others);
+
+            let embed = CreateEmbed::new()
+                .title(locale::tr(locale, K::TEXTS.team))
+                .description(format!(
+                    "<@&{role}>: {}",
+                    locale::format(locale, K::TEXTS.team_members, &[&windows.len(), &members.len()])
+                ))
+                .color(K::COLOR)
+                .field(locale::tr(locale, "Arena"), window::field(&placements, Quadrant::Arena), true)
+                .field(locale::tr(locale, "Blind"), window::field(&placements, Quadrant::Blind), true)
+                .field(locale::tr(locale, "Facade"), window::field(&placements, Quadrant::Facade), true)
+                .field(locale::tr(locale, "Unknown"), window::field(&placements, Quadrant::Unknown), true);
+            respond_embed(&ctx.http, &command, embed, visibility).await;
+        }
+        Some(Subcommand::Diff { since, user, .. }) => {
+            let since = match history::parse_since(&since, store::now()) {
+                Some(since) => since,
+                None => {
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "Expected a date like 2024-01-31 or a duration like 30d",
+                        visibility,
+                    )
+                    .await;
+                    return;
+                }
+            };
+            let target_id = user.unwrap_or(id);
+            if target_id != id && !can_manage_guild(&command) {
+                respond_embed_error(
+                    &ctx.http,
+                    command,
+                    "Only server managers can view the changes to another user's window",
+                    Visibility::Private,
+                )
+                .await;
+                return;
+            }
+            if let Some(target) = group.get(target_id.into()) {
+                let moved = window::movements(&target.placements(since), &target.placements(u64::MAX));
+
+                let embed = CreateEmbed::new()
+                    .title(locale::tr(locale, K::TEXTS.changes))
+                    .description(format!(
+                        "{}\n{}",
+                        locale::format(locale, "{} since {}", &[&format!("<@{target_id}>"), &format!("<t:{since}:D>")]),
+                        if moved.is_empty() {
+                            locale::tr(locale, "No adjectives moved between quadrants").to_string()
+                        } else {
+                            moved
+                                .iter()
+                                .map(|movement| {
+                                    format!(
+                                        "{}: {} → {}",
+                                        locale::adjective(locale, movement.adjective),
+                                        locale::tr(locale, &movement.from.to_string()),
+                                        locale::tr(locale, &movement.to.to_string())
+                                    )
+                                })
+                                .join("\n")
+                        }
+                    ))
+                    .color(K::COLOR);
+                respond_embed(&ctx.http, &command, embed, visibility).await;
+            } else {
+                respond_embed_error(&ctx.http, command, K::TEXTS.missing_user, visibility).await;
+            }
+        }
+        Some(Subcommand::History { user, .. }) => {
+            let target_id = user.unwrap_or(id);
+            if target_id != id && !can_manage_guild(&command) {
+                respond_embed_error(
+                    &ctx.http,
+                    command,
+                    "Only server managers can view the changes to another user's window",
+                    Visibility::Private,
+                )
+                .await;
+                return;
+            }
+            if let Some(target) = group.get(target_id.into()) {
+                let mut entries = history::timeline(locale, "Self-assessment", &target.submissions());
+                let mut description = format!("<@{target_id}>\n");
+                if K::ANONYMOUS {
+                    // When each contribution was made would tell the contributors apart.
+                    description.push_str(&history::render(locale, entries, 20));
+                    description.push_str(&format!(
+                        "\n{}",
+                        locale::format(locale, "{} peer contributions", &[&target.others.len()])
+                    ));
+                } else {
+                    for other in &target.others {
+                        entries.extend(history::timeline(locale, "Peer contribution", &other.submissions()));
+                    }
+                    description.push_str(&history::render(locale, entries, 20));
+                }
+
+                let embed = CreateEmbed::new()
+                    .title(locale::tr(locale, K::TEXTS.history))
+                    .description(description)
+                    .color(K::COLOR);
+                respond_embed(&ctx.http, &command, embed, visibility).await;
+            } else {
+                respond_embed_error(&ctx.http, command, K::TEXTS.missing_user, visibility).await;
+            }
+        }
+        None => {
+            respond_embed_error(
+                &ctx.http,
+                command,
+                "The program reached a (supposedly) unreachable state. Something went wrong",
+                visibility,
+            )
+            .await;
+            panic!("Unreachable state ({} matching error)", K::STORE);
+        }
+    }
+}
@@ -1,3 +1,12 @@
+pub mod admin;
+pub mod comments;
+pub mod competency;
+pub mod custom;
+pub mod engine;
 pub mod johari;
 pub mod nohari;
+pub mod panel;
+pub mod picker;
+pub mod soshari;
 pub mod util;
+pub mod values;
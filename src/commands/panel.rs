@@ -0,0 +1,121 @@
+use serenity::prelude::*;
+use serenity::{
+    builder::{CreateActionRow, CreateButton, CreateEmbed, CreateMessage},
+    model::prelude::{component::ButtonStyle, CommandInteraction, ComponentInteraction},
+};
+
+use crate::locale;
+
+use super::{
+    engine::{self, Subcommand},
+    johari::JohariAdjectives,
+    nohari::NohariAdjectives,
+    util::{can_manage_guild, respond_embed, respond_embed_error, Visibility},
+};
+
+/// Prefix of the custom ids of panel buttons, routed to [`run`] by the event handler.
+pub const PREFIX: &str = "panel:";
+// Custom ids are stored in posted panels, so they must not change between releases.
+const TAKE_JOHARI: &str = "panel:take-johari";
+const TAKE_NOHARI: &str = "panel:take-nohari";
+const VIEW: &str = "panel:view";
+
+/// Posts a panel in the channel `command` was used in, letting members start a window
+/// test or view their window with a click. Panels keep working across restarts as
+/// clicks are handled by the event handler rather than a collector.
+pub async fn post(ctx: Context, command: CommandInteraction) {
+    if command.guild_id.is_none() {
+        respond_embed_error(
+            &ctx.http,
+            command,
+            "The panel can only be posted in a server",
+            Visibility::Private,
+        )
+        .await;
+        return;
+    }
+    if !can_manage_guild(&command) {
+        respond_embed_error(
+            &ctx.http,
+            command,
+            "You need the Manage Server permission to post a panel",
+            Visibility::Private,
+        )
+        .await;
+        return;
+    }
+
+    // The panel is shared by every member, so it follows the server's language.
+    let locale = command.guild_locale.as_deref().unwrap_or(locale::DEFAULT);
+    let embed = CreateEmbed::new()
+        .title(locale::tr(locale, "Soshari windows"))
+        .description(locale::tr(
+            locale,
+            "Take a window test or view your own johari window",
+        ))
+        .color((0xFF, 0x5C, 0x5C));
+    let buttons = CreateActionRow::Buttons(vec![
+        CreateButton::new(locale::tr(locale, "Take Johari"), TAKE_JOHARI)
+            .style(ButtonStyle::Primary),
+        CreateButton::new(locale::tr(locale, "Take Nohari"), TAKE_NOHARI)
+            .style(ButtonStyle::Primary),
+        CreateButton::new(locale::tr(locale, "View my window"), VIEW).style(ButtonStyle::Secondary),
+    ]);
+
+    if let Err(error) = command
+        .channel_id
+        .send_message(
+            &ctx.http,
+            CreateMessage::new().embed(embed).components(vec![buttons]),
+        )
+        .await
+    {
+        eprintln!("Cannot post the panel: {error}");
+        respond_embed_error(
+            &ctx.http,
+            command,
+            "The panel could not be posted in this channel",
+            Visibility::Private,
+        )
+        .await;
+        return;
+    }
+
+    let embed = CreateEmbed::new()
+        .title(locale::tr(&command.locale, "Panel posted"))
+        .description(locale::tr(
+            &command.locale,
+            "The panel has been posted in this channel",
+        ));
+    respond_embed(&ctx.http, &command, embed, Visibility::Private).await;
+}
+
+/// Runs the panel button clicked in `component` as the matching window subcommand.
+pub async fn run(ctx: Context, component: ComponentInteraction) {
+    match component.data.custom_id.as_str() {
+        TAKE_JOHARI => {
+            let subcommand = Subcommand::Start {
+                user: None,
+                guild: None,
+            };
+            engine::execute::<JohariAdjectives>(ctx, component, Some(subcommand)).await;
+        }
+        TAKE_NOHARI => {
+            let subcommand = Subcommand::Start {
+                user: None,
+                guild: None,
+            };
+            engine::execute::<NohariAdjectives>(ctx, component, Some(subcommand)).await;
+        }
+        VIEW => {
+            // Panels are shared by the whole channel, so the window is only shown to
+            // whoever clicked.
+            let subcommand = Subcommand::Query {
+                user: component.user.id,
+                private: Some(true),
+            };
+            engine::execute::<JohariAdjectives>(ctx, component, Some(subcommand)).await;
+        }
+        custom_id => eprintln!("Unknown panel button {custom_id}"),
+    }
+}
@@ -0,0 +1,583 @@
+use std::{io, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use serenity::prelude::*;
+use serenity::{
+    builder::{
+        CreateActionRow, CreateButton, CreateEmbed, CreateInputText, CreateInteractionResponse,
+        CreateInteractionResponseMessage, CreateModal, CreateSelectMenu, CreateSelectMenuKind,
+        CreateSelectMenuOption,
+    },
+    model::prelude::{
+        component::{ActionRowComponent, ButtonStyle, InputTextStyle},
+        ComponentInteraction, ComponentInteractionDataKind, GuildId, MessageId, ModalInteraction,
+        ReactionType, UserId,
+    },
+};
+
+use crate::audit::{self, Action};
+use crate::{locale, sets::SetsLock, settings, store, window};
+
+use super::{
+    competency::{self, CompetencyAdjectives},
+    custom, engine,
+    johari::{self, JohariAdjectives},
+    nohari::{self, NohariAdjectives},
+    util::{lock, respond_embed_error, Adjective, Invocation, Visibility},
+    values::{self, ValuesAdjectives},
+};
+
+pub const STORE: &str = "pickers";
+/// Prefix of the custom ids of picker components, routed to [`run`] by the event handler.
+pub const PREFIX: &str = "picker:";
+// Custom ids are stored in open pickers, so they must not change between releases.
+const SELECT: &str = "picker:select";
+const PREV: &str = "picker:prev";
+const NEXT: &str = "picker:next";
+const SUBMIT: &str = "picker:submit";
+/// Custom id of both the comment button and the modal it opens.
+const COMMENT: &str = "picker:comment";
+const COMMENT_INPUT: &str = "comment";
+/// The longest comment a contribution can carry.
+const MAX_COMMENT: u16 = 500;
+/// Seconds a picker stays usable after its last change.
+const TIMEOUT: u64 = 600;
+/// Discord's limit on the options of a select menu.
+const PAGE_SIZE: usize = 25;
+
+/// What a picker offers: the embed shown above the menu, the adjectives to pick from
+/// and how many of them a submission takes.
+pub struct Picker {
+    pub embed: CreateEmbed,
+    pub adjectives: Vec<Adjective>,
+    pub min: usize,
+    pub max: usize,
+    /// Whether contributions are audited without their contributor.
+    pub anonymous: bool,
+}
+
+impl Picker {
+    /// Names of the adjectives in `selected` still offered, in the picker's order.
+    fn selection(&self, selected: &[String]) -> Vec<String> {
+        self.adjectives
+            .iter()
+            .filter(|adjective| selected.contains(&adjective.name))
+            .map(|adjective| adjective.name.clone())
+            .collect()
+    }
+
+    /// The index of the last page, every page but it holding [`PAGE_SIZE`] adjectives.
+    fn last_page(&self) -> usize {
+        self.adjectives.len().saturating_sub(1) / PAGE_SIZE
+    }
+
+    fn page(&self, page: usize) -> &[Adjective] {
+        let start = (PAGE_SIZE * page).min(self.adjectives.len());
+        let end = (start + PAGE_SIZE).min(self.adjectives.len());
+        &self.adjectives[start..end]
+    }
+
+    /// The picker showing `session`, with a comment button if `comments` are allowed.
+    fn message(
+        &self,
+        locale: &str,
+        session: &Session,
+        comments: bool,
+    ) -> CreateInteractionResponseMessage {
+        let selection = self.selection(&session.selected);
+        let mut embed = self.embed.clone().field(
+            locale::tr(locale, "Selected"),
+            if selection.is_empty() {
+                locale::tr(locale, "Nothing selected yet").into()
+            } else {
+                let labels = self
+                    .adjectives
+                    .iter()
+                    .filter(|adjective| selection.contains(&adjective.name))
+                    .map(|adjective| locale::adjective(locale, &adjective.label).to_string())
+                    .collect::<Vec<_>>();
+                window::lines(&labels, window::MAX_FIELD_LENGTH)
+            },
+            true,
+        );
+        if let Some(comment) = &session.comment {
+            embed = embed.field(locale::tr(locale, "Comment"), comment, false);
+        }
+
+        let options: Vec<_> = self
+            .page(session.page)
+            .iter()
+            .map(|adjective| {
+                let mut option = CreateSelectMenuOption::new(
+                    locale::adjective(locale, &adjective.label),
+                    &adjective.name,
+                )
+                .default_selection(selection.contains(&adjective.name));
+                if let Some(description) = &adjective.description {
+                    option = option.description(locale::tr(locale, description));
+                }
+                if let Some(emoji) = &adjective.emoji {
+                    option = option.emoji(ReactionType::Unicode(emoji.clone()));
+                }
+                option
+            })
+            .collect();
+        let n = options.len() as u64;
+        let select_menu = CreateActionRow::SelectMenu(
+            CreateSelectMenu::new(SELECT, CreateSelectMenuKind::String { options })
+                .min_values(0)
+                .max_values(n),
+        );
+        let mut buttons = vec![
+            CreateButton::new(locale::tr(locale, "Prev"), PREV).style(ButtonStyle::Primary),
+            CreateButton::new(locale::tr(locale, "Next"), NEXT).style(ButtonStyle::Primary),
+            CreateButton::new(locale::tr(locale, "Submit"), SUBMIT)
+                .style(ButtonStyle::Success)
+                .disabled(selection.len() < self.min || selection.len() > self.max),
+        ];
+        // Comments are notes for someone else, so self-assessments do not take one.
+        if session.target.is_some() && comments {
+            buttons.push(
+                CreateButton::new(locale::tr(locale, "Add a comment"), COMMENT)
+                    .style(ButtonStyle::Secondary),
+            );
+        }
+        let buttons = CreateActionRow::Buttons(buttons);
+
+        CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .components(vec![select_menu, buttons])
+    }
+}
+
+/// An open picker, saved on every change so it resumes after a restart.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Session {
+    /// The message showing the picker.
+    pub message_id: u64,
+    pub user_id: u64,
+    /// A built-in window type or, as those names are reserved, an adjective set of
+    /// `guild_id`.
+    pub window: String,
+    /// The user contributed to, `None` for a self-assessment.
+    pub target: Option<u64>,
+    /// The guild the contribution is made from.
+    pub guild_id: Option<u64>,
+    pub selected: Vec<String>,
+    /// The page of adjectives shown in the select menu.
+    pub page: usize,
+    pub timestamp: u64,
+    /// The comment to leave with the contribution.
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+impl Session {
+    pub fn user_id(&self) -> UserId {
+        UserId::new(self.user_id)
+    }
+
+    pub fn target(&self) -> Option<UserId> {
+        self.target.map(UserId::new)
+    }
+
+    pub fn guild_id(&self) -> Option<GuildId> {
+        self.guild_id.map(GuildId::new)
+    }
+
+    /// Whether the guild the contribution is made from allows comments, denying them
+    /// when its settings cannot be read.
+    fn comments_allowed(&self) -> bool {
+        settings::comments_allowed(self.guild_id()).unwrap_or_else(|error| {
+            eprintln!("Cannot load the guild settings: {error}");
+            false
+        })
+    }
+
+    fn is_expired(&self, now: u64) -> bool {
+        now >= self.timestamp + TIMEOUT
+    }
+
+    /// Replaces the selection among `shown`, the adjectives of the current page, with
+    /// `values`.
+    fn select(&mut self, shown: &[Adjective], values: &[String]) {
+        let shown = |name: &String| shown.iter().any(|adjective| &adjective.name == name);
+        self.selected.retain(|name| !shown(name));
+        self.selected
+            .extend(values.iter().filter(|name| shown(name)).cloned());
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(transparent)]
+struct Sessions(Vec<Session>);
+
+/// Guards the saved sessions, as clicks are handled concurrently and each one loads them
+/// and writes them back. Inserted into the client data at startup.
+pub struct SessionLock;
+
+impl TypeMapKey for SessionLock {
+    type Value = Arc<Mutex<()>>;
+}
+
+impl Sessions {
+    fn load() -> io::Result<Self> {
+        store::load(STORE)
+    }
+
+    fn dump(&self) -> io::Result<()> {
+        store::dump(STORE, self)
+    }
+
+    /// Removes the session of message `id`, if it has not expired.
+    fn take(&mut self, id: MessageId, now: u64) -> Option<Session> {
+        let index = self
+            .0
+            .iter()
+            .position(|session| session.message_id == u64::from(id))?;
+        Some(self.0.remove(index)).filter(|session| !session.is_expired(now))
+    }
+
+    fn insert(&mut self, session: Session, now: u64) {
+        self.0.retain(|session| !session.is_expired(now));
+        self.0.push(session);
+    }
+}
+
+/// Shows `picker` to the user of `command` only, to contribute to `target`'s `window`
+/// or to assess themselves. Every click is handled by [`run`], so the picker keeps
+/// working across restarts.
+pub async fn open(
+    ctx: &Context,
+    command: &impl Invocation,
+    picker: &Picker,
+    window: &str,
+    target: Option<UserId>,
+    guild_id: Option<GuildId>,
+) {
+    let mut session = Session {
+        message_id: 0,
+        user_id: command.user().id.into(),
+        window: window.to_string(),
+        target: target.map(u64::from),
+        guild_id: guild_id.map(u64::from),
+        selected: Vec::new(),
+        page: 0,
+        timestamp: store::now(),
+        comment: None,
+    };
+
+    command
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(Visibility::Private.apply(picker.message(
+                command.locale(),
+                &session,
+                session.comments_allowed(),
+            ))),
+        )
+        .await
+        .unwrap();
+    let message = command.get_response(&ctx.http).await.unwrap();
+    session.message_id = message.id.into();
+
+    let lock = lock::<SessionLock>(ctx).await;
+    let _guard = lock.lock().await;
+    let saved = Sessions::load().and_then(|mut sessions| {
+        sessions.insert(session, store::now());
+        sessions.dump()
+    });
+    if let Err(error) = saved {
+        eprintln!("Cannot save the picker session: {error}");
+    }
+}
+
+/// The picker of `session`'s window, shown to `locale`.
+fn picker(session: &Session, locale: &str) -> Result<Picker, &'static str> {
+    match session.window.as_str() {
+        johari::STORE => Ok(engine::picker::<JohariAdjectives>(locale)),
+        nohari::STORE => Ok(engine::picker::<NohariAdjectives>(locale)),
+        competency::STORE => Ok(engine::picker::<CompetencyAdjectives>(locale)),
+        values::STORE => Ok(engine::picker::<ValuesAdjectives>(locale)),
+        set => custom::picker(session.guild_id(), set, locale),
+    }
+}
+
+/// Records `selected` as the submission of `session`, returning the action to audit.
+async fn submit(
+    ctx: &Context,
+    session: &Session,
+    selected: Vec<String>,
+) -> Result<Action, &'static str> {
+    match session.window.as_str() {
+        johari::STORE => engine::submit::<JohariAdjectives>(session, selected),
+        nohari::STORE => engine::submit::<NohariAdjectives>(session, selected),
+        competency::STORE => engine::submit::<CompetencyAdjectives>(session, selected),
+        values::STORE => engine::submit::<ValuesAdjectives>(session, selected),
+        _ => {
+            let lock = lock::<SetsLock>(ctx).await;
+            let _guard = lock.lock().await;
+            custom::submit(session, selected)
+        }
+    }
+}
+
+/// Loads the session of the picker shown to `user` in message `id`, along with the
+/// picker itself.
+fn resume(
+    id: MessageId,
+    user: UserId,
+    locale: &str,
+    now: u64,
+) -> Result<(Sessions, Session, Picker), &'static str> {
+    let mut sessions = Sessions::load().map_err(|error| {
+        eprintln!("Cannot load the picker sessions: {error}");
+        "The picker sessions could not be read"
+    })?;
+    let session = sessions
+        .take(id, now)
+        .filter(|session| session.user_id() == user)
+        .ok_or("This picker has expired, please start again")?;
+    let picker = picker(&session, locale)?;
+    Ok((sessions, session, picker))
+}
+
+/// Puts `session` back among `sessions` after a change and saves them.
+fn save(mut sessions: Sessions, mut session: Session, now: u64) {
+    session.timestamp = now;
+    sessions.insert(session, now);
+    if let Err(error) = sessions.dump() {
+        eprintln!("Cannot save the picker sessions: {error}");
+    }
+}
+
+/// Handles a click on a picker, resuming its saved session.
+pub async fn run(ctx: Context, component: ComponentInteraction) {
+    let now = store::now();
+    let locale = component.locale.as_str();
+    // Held until the sessions are saved, so concurrent clicks do not overwrite each other.
+    let lock = lock::<SessionLock>(&ctx).await;
+    let guard = lock.lock().await;
+    let (sessions, mut session, picker) =
+        match resume(component.message.id, component.user.id, locale, now) {
+            Ok(resumed) => resumed,
+            Err(message) => {
+                drop(guard);
+                respond_embed_error(&ctx.http, component, message, Visibility::Private).await;
+                return;
+            }
+        };
+
+    match component.data.custom_id.as_str() {
+        SELECT => {
+            if let ComponentInteractionDataKind::StringSelect { values } = &component.data.kind {
+                session.select(picker.page(session.page), values);
+            }
+        }
+        PREV => session.page = session.page.saturating_sub(1),
+        NEXT => session.page = (session.page + 1).min(picker.last_page()),
+        COMMENT if session.comments_allowed() => {
+            let mut input = CreateInputText::new(
+                InputTextStyle::Paragraph,
+                locale::tr(locale, "Comment"),
+                COMMENT_INPUT,
+            )
+            .placeholder(locale::tr(
+                locale,
+                "A short note, shown to them without your name",
+            ))
+            .max_length(MAX_COMMENT)
+            .required(false);
+            if let Some(comment) = &session.comment {
+                input = input.value(comment);
+            }
+            let modal = CreateModal::new(COMMENT, locale::tr(locale, "Add a comment"))
+                .components(vec![CreateActionRow::InputText(input)]);
+
+            // The session is left saved as it was until the modal is submitted.
+            drop(guard);
+            component
+                .create_response(&ctx.http, CreateInteractionResponse::Modal(modal))
+                .await
+                .unwrap();
+            return;
+        }
+        SUBMIT => {
+            let selected = picker.selection(&session.selected);
+            if (picker.min..=picker.max).contains(&selected.len()) {
+                // Comments may have been disabled since the picker was opened.
+                if !session.comments_allowed() {
+                    session.comment = None;
+                }
+                // The session stays saved unless the submission is recorded, so a failed
+                // one can be retried.
+                let action = match submit(&ctx, &session, selected).await {
+                    Ok(action) => action,
+                    Err(message) => {
+                        drop(guard);
+                        respond_embed_error(&ctx.http, component, message, Visibility::Private)
+                            .await;
+                        return;
+                    }
+                };
+                if let Err(error) = sessions.dump() {
+                    eprintln!("Cannot save the picker sessions: {error}");
+                }
+                drop(guard);
+
+                component
+                    .create_response(
+                        &ctx.http,
+                        CreateInteractionResponse::UpdateMessage(
+                            CreateInteractionResponseMessage::new()
+                                .embed(
+                                    CreateEmbed::new()
+                                        .title(locale::tr(locale, "Submission recorded"))
+                                        .description(locale::tr(
+                                            locale,
+                                            "Your submission has been recorded",
+                                        )),
+                                )
+                                .components(Vec::new()),
+                        ),
+                    )
+                    .await
+                    .unwrap();
+                let user = match session.target {
+                    Some(_) if picker.anonymous => None,
+                    _ => Some(session.user_id()),
+                };
+                audit::record(&ctx, session.guild_id(), user, action).await;
+                if let (Some(target), Some(_)) = (session.target, &session.comment) {
+                    let action = Action::CommentLeft {
+                        window: session.window.clone(),
+                        target,
+                    };
+                    audit::record(&ctx, session.guild_id(), None, action).await;
+                }
+                return;
+            }
+        }
+        _ => {}
+    }
+
+    let message = picker.message(locale, &session, session.comments_allowed());
+    save(sessions, session, now);
+    drop(guard);
+    component
+        .create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(message))
+        .await
+        .unwrap();
+}
+
+/// Handles the comment modal of a picker, keeping the comment in its session until the
+/// picker is submitted.
+pub async fn run_modal(ctx: Context, modal: ModalInteraction) {
+    let now = store::now();
+    let lock = lock::<SessionLock>(&ctx).await;
+    let guard = lock.lock().await;
+    let resumed = match &modal.message {
+        Some(message) => resume(message.id, modal.user.id, &modal.locale, now),
+        None => Err("This picker has expired, please start again"),
+    };
+    let (sessions, mut session, picker) = match resumed {
+        Ok(resumed) => resumed,
+        Err(message) => {
+            drop(guard);
+            respond_embed_error(&ctx.http, modal, message, Visibility::Private).await;
+            return;
+        }
+    };
+
+    let comment = modal
+        .data
+        .components
+        .iter()
+        .flat_map(|row| &row.components)
+        .find_map(|component| match component {
+            ActionRowComponent::InputText(input) if input.custom_id == COMMENT_INPUT => {
+                input.value.clone()
+            }
+            _ => None,
+        });
+    let comments = session.comments_allowed();
+    session.comment = comment
+        .map(|comment| comment.trim().to_string())
+        .filter(|comment| comments && !comment.is_empty());
+
+    let message = picker.message(&modal.locale, &session, comments);
+    save(sessions, session, now);
+    drop(guard);
+    modal
+        .create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(message))
+        .await
+        .unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adjective(name: &str) -> Adjective {
+        Adjective {
+            name: name.to_string(),
+            label: name.to_string(),
+            description: None,
+            emoji: None,
+        }
+    }
+
+    fn session() -> Session {
+        Session {
+            message_id: 1,
+            user_id: 2,
+            window: johari::STORE.to_string(),
+            target: None,
+            guild_id: None,
+            selected: Vec::new(),
+            page: 0,
+            timestamp: 100,
+            comment: None,
+        }
+    }
+
+    #[test]
+    fn selects_within_the_shown_page() {
+        let first = [adjective("bold"), adjective("calm")];
+        let second = [adjective("shy")];
+        let mut session = session();
+
+        session.select(&first, &["bold".to_string(), "calm".to_string()]);
+        session.select(&second, &["shy".to_string()]);
+        session.select(&first, &["calm".to_string()]);
+        assert_eq!(session.selected, vec!["shy", "calm"]);
+    }
+
+    #[test]
+    fn pages_end_at_the_last_adjective() {
+        let picker = |count: usize| Picker {
+            embed: CreateEmbed::new(),
+            adjectives: (0..count).map(|i| adjective(&i.to_string())).collect(),
+            min: 0,
+            max: count,
+            anonymous: false,
+        };
+
+        assert_eq!(picker(24).last_page(), 0);
+        assert_eq!(picker(25).last_page(), 0);
+        assert_eq!(picker(26).last_page(), 1);
+        assert_eq!(picker(50).last_page(), 1);
+        assert_eq!(picker(50).page(1).len(), PAGE_SIZE);
+        assert_eq!(picker(0).last_page(), 0);
+    }
+
+    #[test]
+    fn expired_sessions_are_not_resumed() {
+        let mut sessions = Sessions(vec![session()]);
+        assert!(sessions.take(MessageId::new(1), 100 + TIMEOUT).is_none());
+        assert!(sessions.0.is_empty());
+
+        let mut sessions = Sessions(vec![session()]);
+        assert!(sessions.take(MessageId::new(1), 100).is_some());
+    }
+}
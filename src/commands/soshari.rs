@@ -0,0 +1,275 @@
+use itertools::Itertools;
+
+use serenity::prelude::*;
+use serenity::{
+    builder::{
+        CreateCommand, CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage,
+    },
+    model::prelude::{CommandInteraction, Permissions, UserId},
+};
+use soshari_macros::SlashCommand;
+
+use crate::{
+    locale,
+    sets::{self, SetsLock},
+    store,
+};
+
+use super::{
+    admin::{self, AdminCommand},
+    competency::CompetencyAdjectives,
+    custom::{self, SetCommand},
+    engine,
+    johari::JohariAdjectives,
+    nohari::NohariAdjectives,
+    panel,
+    util::{lock, mentions, respond_embed_error, Visibility},
+    values::ValuesAdjectives,
+};
+
+#[derive(SlashCommand)]
+#[command(name = "soshari", description = "Manage the soshari bot")]
+#[localized(locale = "de", description = "Verwalte den soshari-Bot")]
+pub enum SoshariCommand {
+    #[command(description = "List the snapshots window data can be restored from")]
+    #[localized(
+        locale = "de",
+        description = "Liste die Sicherungen auf, aus denen Fensterdaten wiederhergestellt werden können"
+    )]
+    Snapshots,
+    #[command(description = "Restore window data from a snapshot")]
+    #[localized(
+        locale = "de",
+        description = "Stelle Fensterdaten aus einer Sicherung wieder her"
+    )]
+    Restore {
+        #[option(description = "Name of the snapshot to restore")]
+        #[localized(
+            locale = "de",
+            description = "Name der wiederherzustellenden Sicherung"
+        )]
+        snapshot: String,
+        #[option(description = "Only show what restoring would change")]
+        #[localized(
+            locale = "de",
+            description = "Zeige nur, was die Wiederherstellung ändern würde"
+        )]
+        dry_run: Option<bool>,
+    },
+    #[command(description = "Moderate the window data of this server's members")]
+    #[localized(
+        locale = "de",
+        description = "Moderiere die Fensterdaten der Mitglieder dieses Servers"
+    )]
+    Admin(AdminCommand),
+    #[command(description = "Manage this server's adjective sets")]
+    #[localized(
+        locale = "de",
+        description = "Verwalte die Adjektivlisten dieses Servers"
+    )]
+    Sets(SetCommand),
+    #[command(description = "Post a panel with buttons to take and view windows")]
+    #[localized(
+        locale = "de",
+        description = "Poste ein Panel mit Knöpfen, um Fenster-Tests zu starten und Fenster anzusehen"
+    )]
+    Panel,
+}
+
+/// Hidden from members without Manage Server by default; admin subcommands check the
+/// permission again and the others are restricted to the bot owner.
+pub fn create() -> CreateCommand {
+    SoshariCommand::create().default_member_permissions(Permissions::MANAGE_GUILD)
+}
+
+/// Window data is shared by every guild, so only the bot owner may replace it.
+async fn is_owner(ctx: &Context, id: UserId) -> bool {
+    match ctx.http.get_current_application_info().await {
+        Ok(info) => info.owner.map(|owner| owner.id) == Some(id),
+        Err(_) => false,
+    }
+}
+
+/// Only the admin subcommands take options with suggestions.
+pub async fn autocomplete(ctx: Context, interaction: CommandInteraction) {
+    admin::autocomplete(ctx, interaction).await;
+}
+
+pub async fn run(ctx: Context, command: CommandInteraction) {
+    let subcommand = match SoshariCommand::parse(&command) {
+        Some(SoshariCommand::Admin(admin)) => {
+            admin::run(ctx, command, admin).await;
+            return;
+        }
+        Some(SoshariCommand::Sets(sets)) => {
+            custom::run_sets(ctx, command, sets).await;
+            return;
+        }
+        Some(SoshariCommand::Panel) => {
+            panel::post(ctx, command).await;
+            return;
+        }
+        subcommand => subcommand,
+    };
+
+    if !is_owner(&ctx, command.user.id).await {
+        respond_embed_error(
+            &ctx.http,
+            command,
+            "Only the bot owner can manage window data",
+            Visibility::Private,
+        )
+        .await;
+        return;
+    }
+
+    let locale = command.locale.as_str();
+    let embed = match subcommand {
+        Some(SoshariCommand::Snapshots) => {
+            let snapshots = match store::snapshots() {
+                Ok(snapshots) => snapshots,
+                Err(error) => {
+                    eprintln!("Cannot list snapshots: {error}");
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "Cannot list snapshots",
+                        Visibility::Private,
+                    )
+                    .await;
+                    return;
+                }
+            };
+
+            CreateEmbed::new()
+                .title(locale::tr(locale, "Snapshots"))
+                .color((0xFF, 0x5C, 0x5C))
+                .description(if snapshots.is_empty() {
+                    locale::tr(locale, "No snapshots have been taken yet").to_string()
+                } else {
+                    snapshots
+                        .iter()
+                        .rev()
+                        .take(50)
+                        .map(|snapshot| format!("`{}` <t:{}:f>", snapshot.name, snapshot.timestamp))
+                        .join("\n")
+                })
+        }
+        Some(SoshariCommand::Restore { snapshot, dry_run }) => {
+            let dry_run = dry_run.unwrap_or(false);
+            let snapshot = match store::snapshot(&snapshot) {
+                Ok(Some(snapshot)) => snapshot,
+                _ => {
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "Cannot find the snapshot",
+                        Visibility::Private,
+                    )
+                    .await;
+                    return;
+                }
+            };
+
+            let diff = match snapshot.store.as_str() {
+                "johari" => engine::restore::<JohariAdjectives>(&snapshot, dry_run),
+                "nohari" => engine::restore::<NohariAdjectives>(&snapshot, dry_run),
+                "competency" => engine::restore::<CompetencyAdjectives>(&snapshot, dry_run),
+                "values" => engine::restore::<ValuesAdjectives>(&snapshot, dry_run),
+                sets::STORE => {
+                    let lock = lock::<SetsLock>(&ctx).await;
+                    let _guard = lock.lock().await;
+                    custom::restore(&snapshot, dry_run)
+                }
+                _ => {
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "Unknown snapshot store",
+                        Visibility::Private,
+                    )
+                    .await;
+                    return;
+                }
+            };
+            let diff = match diff {
+                Ok(diff) => diff,
+                Err(error) => {
+                    eprintln!("Cannot restore {}: {error}", snapshot.name);
+                    respond_embed_error(
+                        &ctx.http,
+                        command,
+                        "Cannot restore the snapshot",
+                        Visibility::Private,
+                    )
+                    .await;
+                    return;
+                }
+            };
+
+            CreateEmbed::new()
+                .title(locale::tr(
+                    locale,
+                    if dry_run {
+                        "Restore preview"
+                    } else {
+                        "Snapshot restored"
+                    },
+                ))
+                .description(format!(
+                    "{}{}",
+                    locale::format(
+                        locale,
+                        "{} from {}",
+                        &[
+                            &format!("`{}`", snapshot.name),
+                            &format!("<t:{}:f>", snapshot.timestamp)
+                        ]
+                    ),
+                    if diff.is_empty() {
+                        format!(
+                            "\n{}",
+                            locale::tr(locale, "The snapshot matches the current data")
+                        )
+                    } else {
+                        String::new()
+                    }
+                ))
+                .color((0xFF, 0x5C, 0x5C))
+                .field(locale::tr(locale, "Added"), mentions(&diff.added), false)
+                .field(
+                    locale::tr(locale, "Removed"),
+                    mentions(&diff.removed),
+                    false,
+                )
+                .field(
+                    locale::tr(locale, "Changed"),
+                    mentions(&diff.changed),
+                    false,
+                )
+        }
+        Some(SoshariCommand::Admin(_))
+        | Some(SoshariCommand::Sets(_))
+        | Some(SoshariCommand::Panel)
+        | None => {
+            respond_embed_error(
+                &ctx.http,
+                command,
+                "The program reached a (supposedly) unreachable state. Something went wrong",
+                Visibility::Private,
+            )
+            .await;
+            panic!("Unreachable state (soshari matching error)");
+        }
+    };
+
+    command
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new().embed(embed),
+            ),
+        )
+        .await
+        .unwrap();
+}
@@ -1,198 +1,464 @@
-use std::{collections::HashMap, time::Duration};
+use std::{collections::HashSet, sync::Arc};
 
 use itertools::Itertools;
 use serenity::{
     builder::{
-        CreateActionRow, CreateButton, CreateEmbed, CreateInteractionResponse,
-        CreateInteractionResponseMessage, CreateSelectMenu, CreateSelectMenuKind,
-        CreateSelectMenuOption,
+        CreateActionRow, CreateAutocompleteResponse, CreateEmbed, CreateInteractionResponse,
+        CreateInteractionResponseMessage,
     },
-    collector::ComponentInteractionCollectorBuilder,
-    futures::StreamExt,
+    futures::{future::join_all, StreamExt},
     http::Http,
-    model::prelude::{component::ButtonStyle, CommandInteraction, ComponentInteractionDataKind},
+    model::prelude::{
+        CommandInteraction, ComponentInteraction, GuildId, Member, Message, ModalInteraction,
+        ResolvedOption, ResolvedValue, User, UserId,
+    },
     prelude::*,
 };
 
-pub async fn respond_embed_error(
-    http: impl AsRef<Http>,
-    interaction: CommandInteraction,
-    message: &str,
-) {
-    let embed = CreateEmbed::new()
-        .title("Error")
-        .color((255, 0, 0))
-        .description(message);
-    interaction
-        .create_response(
-            http,
-            CreateInteractionResponse::Message(
-                CreateInteractionResponseMessage::new().embed(embed),
-            ),
-        )
+use crate::locale;
+
+/// An adjective offered by a [`Picker`](super::picker::Picker), from a built-in
+/// `#[adjectives]` list or a guild's custom set.
+pub struct Adjective {
+    pub name: String,
+    pub label: String,
+    pub description: Option<String>,
+    pub emoji: Option<String>,
+}
+
+/// The lock `L` guarding a store that is loaded, changed and written back, from the
+/// client data it is inserted into at startup.
+pub async fn lock<L>(ctx: &Context) -> Arc<Mutex<()>>
+where
+    L: TypeMapKey<Value = Arc<Mutex<()>>>,
+{
+    ctx.data
+        .read()
         .await
-        .unwrap();
+        .get::<L>()
+        .cloned()
+        .expect("Store locks are inserted at startup")
 }
 
-pub async fn menu_get(
-    embed: CreateEmbed,
-    ctx: &Context,
-    command: &CommandInteraction,
-    adjectives: &[&str],
-    min: usize,
-) -> Vec<String> {
-    let embed = |selected: &Vec<String>| {
-        embed.clone().field(
-            "Selected",
-            if selected.is_empty() {
-                "Nothing selected yet".into()
-            } else {
-                selected.join("\n")
-            },
-            true,
-        )
-    };
+/// Whether a response is shown to the whole channel or only to the invoking user.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Visibility {
+    Public,
+    Private,
+}
+
+impl Visibility {
+    pub fn private(private: bool) -> Self {
+        if private {
+            Visibility::Private
+        } else {
+            Visibility::Public
+        }
+    }
+
+    pub fn apply(
+        self,
+        message: CreateInteractionResponseMessage,
+    ) -> CreateInteractionResponseMessage {
+        message.ephemeral(self == Visibility::Private)
+    }
+}
+
+/// An interaction the window engine answers: a slash or user command, a click on a
+/// persistent component such as a panel button, or a submitted modal.
+pub trait Invocation: Sync {
+    fn user(&self) -> &User;
+    fn guild_id(&self) -> Option<GuildId>;
+    fn member(&self) -> Option<&Member>;
+    fn locale(&self) -> &str;
+    async fn create_response(
+        &self,
+        http: impl AsRef<Http>,
+        response: CreateInteractionResponse,
+    ) -> serenity::Result<()>;
+    async fn get_response(&self, http: impl AsRef<Http>) -> serenity::Result<Message>;
+}
+
+impl Invocation for CommandInteraction {
+    fn user(&self) -> &User {
+        &self.user
+    }
+
+    fn guild_id(&self) -> Option<GuildId> {
+        self.guild_id
+    }
+
+    fn member(&self) -> Option<&Member> {
+        self.member.as_deref()
+    }
 
-    let mut selection: HashMap<String, bool> = adjectives
-        .iter()
-        .map(|adjective| (adjective.to_string(), false))
-        .collect();
-
-    let mut last_selected: Vec<String> = Vec::new();
-    let mut selected: Vec<String> = Vec::new();
-
-    let select_menu = |index, selected: &Vec<String>| {
-        let adjectives: Vec<_> = adjectives
-            .iter()
-            .skip(25 * index)
-            .take(25)
-            .map(|adjective| {
-                CreateSelectMenuOption::new(adjective.clone(), adjective.clone()).default_selection(
-                    selected
-                        .iter()
-                        .map(AsRef::<str>::as_ref)
-                        .contains(adjective),
+    fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    async fn create_response(
+        &self,
+        http: impl AsRef<Http>,
+        response: CreateInteractionResponse,
+    ) -> serenity::Result<()> {
+        CommandInteraction::create_response(self, http, response).await
+    }
+
+    async fn get_response(&self, http: impl AsRef<Http>) -> serenity::Result<Message> {
+        CommandInteraction::get_response(self, http).await
+    }
+}
+
+impl Invocation for ComponentInteraction {
+    fn user(&self) -> &User {
+        &self.user
+    }
+
+    fn guild_id(&self) -> Option<GuildId> {
+        self.guild_id
+    }
+
+    fn member(&self) -> Option<&Member> {
+        self.member.as_ref()
+    }
+
+    fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    async fn create_response(
+        &self,
+        http: impl AsRef<Http>,
+        response: CreateInteractionResponse,
+    ) -> serenity::Result<()> {
+        ComponentInteraction::create_response(self, http, response).await
+    }
+
+    async fn get_response(&self, http: impl AsRef<Http>) -> serenity::Result<Message> {
+        ComponentInteraction::get_response(self, http).await
+    }
+}
+
+impl Invocation for ModalInteraction {
+    fn user(&self) -> &User {
+        &self.user
+    }
+
+    fn guild_id(&self) -> Option<GuildId> {
+        self.guild_id
+    }
+
+    fn member(&self) -> Option<&Member> {
+        self.member.as_ref()
+    }
+
+    fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    async fn create_response(
+        &self,
+        http: impl AsRef<Http>,
+        response: CreateInteractionResponse,
+    ) -> serenity::Result<()> {
+        ModalInteraction::create_response(self, http, response).await
+    }
+
+    async fn get_response(&self, http: impl AsRef<Http>) -> serenity::Result<Message> {
+        ModalInteraction::get_response(self, http).await
+    }
+}
+
+/// Mentions the users in `ids`, shortening long lists so they fit in an embed field.
+pub fn mentions(ids: &[u64]) -> String {
+    if ids.is_empty() {
+        "N/A".to_string()
+    } else {
+        let mut mentions = ids.iter().take(30).map(|id| format!("<@{id}>")).join(" ");
+        if ids.len() > 30 {
+            mentions.push_str(&format!(" and {} more", ids.len() - 30));
+        }
+        mentions
+    }
+}
+
+/// Every member of `guild_id`, fetched page by page.
+pub async fn guild_members(ctx: &Context, guild_id: GuildId) -> serenity::Result<Vec<Member>> {
+    let mut members = Vec::new();
+    let mut members_iter = guild_id.members_iter(&ctx.http).boxed();
+    while let Some(member) = members_iter.next().await {
+        members.push(member?);
+    }
+    Ok(members)
+}
+
+/// Most guilds whose membership [`guild_choices`] asks Discord for on each keystroke.
+const MAX_MEMBER_LOOKUPS: usize = 5;
+
+/// The guilds shared by the bot and `user_id` whose name contains `partial`, as
+/// autocomplete choices of their name and id. Guilds that do not cache `user_id` as a
+/// member are only suggested if they are among the first [`MAX_MEMBER_LOOKUPS`] matches.
+pub async fn guild_choices(ctx: &Context, user_id: UserId, partial: &str) -> Vec<(String, String)> {
+    let partial = partial.to_lowercase();
+    let guilds = ctx
+        .cache
+        .guilds()
+        .into_iter()
+        .filter_map(|guild_id| {
+            let guild = ctx.cache.guild(guild_id)?;
+            guild.name.to_lowercase().contains(&partial).then(|| {
+                (
+                    guild_id,
+                    guild.name.clone(),
+                    guild.members.contains_key(&user_id),
                 )
             })
-            .collect();
-        let n = adjectives.len() as u64;
-        CreateActionRow::SelectMenu(
-            CreateSelectMenu::new(
-                "select_menu",
-                CreateSelectMenuKind::String {
-                    options: adjectives,
-                },
-            )
-            .min_values(0)
-            .max_values(n),
-        )
+        })
+        .collect::<Vec<_>>();
+
+    // Members of large guilds are not cached, so membership is asked for instead, for a
+    // few guilds at once to answer within the autocomplete deadline.
+    let (cached, uncached): (Vec<_>, Vec<_>) = guilds.into_iter().partition(|guild| guild.2);
+    let mut choices = cached
+        .into_iter()
+        .map(|(guild_id, name, _)| (name, guild_id.to_string()))
+        .take(25)
+        .collect::<Vec<_>>();
+    let lookups = uncached
+        .into_iter()
+        .take(MAX_MEMBER_LOOKUPS.min(25 - choices.len()))
+        .map(|(guild_id, name, _)| async move {
+            guild_id
+                .member(&ctx.http, user_id)
+                .await
+                .ok()
+                .map(|_| (name, guild_id.to_string()))
+        });
+    choices.extend(join_all(lookups).await.into_iter().flatten());
+    choices
+}
+
+/// The users among `ids` whose name contains `partial`, as autocomplete choices of their
+/// name and id: the members of `guild_id`, or any cached user without a guild. Members
+/// come from the cache, which only holds every member of smaller guilds; in larger ones
+/// members whose name starts with `partial` are searched for instead, so nothing is
+/// suggested there until something is typed.
+pub async fn user_choices(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    ids: impl Iterator<Item = u64>,
+    partial: &str,
+) -> Vec<(String, String)> {
+    let partial = partial.to_lowercase();
+    let ids = ids.filter(|id| *id != 0);
+    let guild_id = match guild_id {
+        Some(guild_id) => guild_id,
+        None => {
+            return ids
+                .filter_map(|id| {
+                    let name = ctx.cache.user(UserId::new(id))?.name.clone();
+                    name.to_lowercase()
+                        .contains(&partial)
+                        .then(|| (name, id.to_string()))
+                })
+                .take(25)
+                .collect()
+        }
     };
-    let buttons = |selected: &Vec<String>| {
-        CreateActionRow::Buttons(vec![
-            CreateButton::new("Prev", "prev").style(ButtonStyle::Primary),
-            CreateButton::new("Next", "next").style(ButtonStyle::Primary),
-            CreateButton::new("Submit", "submit")
-                .style(ButtonStyle::Success)
-                .disabled(selected.len() < min),
-        ])
+
+    let ids = ids.collect::<HashSet<_>>();
+    let cached = ctx.cache.guild(guild_id).map(|guild| {
+        let members = guild
+            .members
+            .values()
+            .map(|member| (member.user.id, member.display_name().to_string()))
+            .collect::<Vec<_>>();
+        (members.len() as u64 >= guild.member_count, members)
+    });
+    let members = match cached {
+        Some((false, _)) | None if !partial.is_empty() => {
+            match guild_id
+                .search_members(&ctx.http, &partial, Some(1000))
+                .await
+            {
+                Ok(members) => members
+                    .iter()
+                    .map(|member| (member.user.id, member.display_name().to_string()))
+                    .collect(),
+                Err(error) => {
+                    eprintln!("Cannot search the members of {guild_id}: {error}");
+                    Vec::new()
+                }
+            }
+        }
+        Some((_, members)) => members,
+        None => Vec::new(),
     };
+    members
+        .into_iter()
+        .filter(|(id, name)| ids.contains(&id.get()) && name.to_lowercase().contains(&partial))
+        .take(25)
+        .map(|(id, name)| (name, id.to_string()))
+        .collect()
+}
 
-    let mut menu_index: usize = 0;
+/// The string option `name` given next to the option being completed by `interaction`.
+pub fn autocomplete_option<'a>(interaction: &'a CommandInteraction, name: &str) -> Option<&'a str> {
+    fn find<'a>(options: Vec<ResolvedOption<'a>>, name: &str) -> Option<&'a str> {
+        options.into_iter().find_map(|option| match option.value {
+            ResolvedValue::SubCommand(options) | ResolvedValue::SubCommandGroup(options) => {
+                find(options, name)
+            }
+            ResolvedValue::String(value) if option.name == name => Some(value),
+            _ => None,
+        })
+    }
+    find(interaction.data.options(), name)
+}
+
+/// The guild an autocomplete `interaction` concerns: the guild it is used in, or in DMs
+/// the guild picked with the `guild` option, if the user is a member of it.
+pub async fn autocomplete_guild(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+) -> Option<GuildId> {
+    if interaction.guild_id.is_some() {
+        return interaction.guild_id;
+    }
+    let guild_id = autocomplete_option(interaction, "guild")?
+        .parse::<u64>()
+        .ok()
+        .filter(|id| *id != 0)
+        .map(GuildId::new)?;
+    let cached = ctx
+        .cache
+        .guild(guild_id)
+        .is_some_and(|guild| guild.members.contains_key(&interaction.user.id));
+    (cached
+        || guild_id
+            .member(&ctx.http, interaction.user.id)
+            .await
+            .is_ok())
+    .then_some(guild_id)
+}
+
+/// Reads a user option filled by [`user_choices`], also accepting a pasted id or mention.
+pub fn parse_user(value: &str) -> Option<UserId> {
+    value
+        .trim()
+        .trim_start_matches("<@")
+        .trim_start_matches('!')
+        .trim_end_matches('>')
+        .parse::<u64>()
+        .ok()
+        .filter(|id| *id != 0)
+        .map(UserId::new)
+}
 
+/// The guild a contribution through `command` is made from: the guild it was used in,
+/// or in DMs the guild picked with the `guild` option, if any.
+pub async fn contribution_guild(
+    ctx: &Context,
+    command: &impl Invocation,
+    guild: Option<String>,
+) -> Result<Option<GuildId>, &'static str> {
+    if command.guild_id().is_some() {
+        return Ok(command.guild_id());
+    }
+    let guild_id = match guild.map(|guild| guild.parse::<u64>()) {
+        Some(Ok(id)) if id != 0 => GuildId::new(id),
+        Some(_) => return Err("Unknown server, pick one of the suggestions"),
+        None => return Ok(None),
+    };
+    match guild_id.member(&ctx.http, command.user().id).await {
+        Ok(_) => Ok(Some(guild_id)),
+        Err(_) => Err("You are not a member of that server"),
+    }
+}
+
+/// Whether the member invoking `command` has the Manage Server permission.
+pub fn can_manage_guild(command: &impl Invocation) -> bool {
     command
+        .member()
+        .and_then(|member| member.permissions)
+        .is_some_and(|permissions| permissions.manage_guild())
+}
+
+pub async fn respond_embed(
+    http: impl AsRef<Http>,
+    interaction: &impl Invocation,
+    embed: CreateEmbed,
+    visibility: Visibility,
+) {
+    respond_embed_components(http, interaction, embed, Vec::new(), visibility).await;
+}
+
+/// Like [`respond_embed`], with `components` below the embed.
+pub async fn respond_embed_components(
+    http: impl AsRef<Http>,
+    interaction: &impl Invocation,
+    embed: CreateEmbed,
+    components: Vec<CreateActionRow>,
+    visibility: Visibility,
+) {
+    interaction
         .create_response(
-            &ctx.http,
+            http,
             CreateInteractionResponse::Message(
-                CreateInteractionResponseMessage::default()
-                    .embed(embed(&selected))
-                    .components(vec![
-                        select_menu(menu_index, &selected).clone(),
-                        buttons(&selected),
-                    ]),
+                visibility.apply(
+                    CreateInteractionResponseMessage::new()
+                        .embed(embed)
+                        .components(components),
+                ),
             ),
         )
         .await
         .unwrap();
+}
 
-    let message = command.get_response(&ctx.http).await.unwrap();
-    let mut collector = ComponentInteractionCollectorBuilder::new(&ctx.shard)
-        .author_id(command.user.id)
-        .message_id(message.id)
-        .timeout(Duration::from_secs(600))
-        .build();
-
-    while let Some(interaction) = collector.next().await {
-        match interaction.data.custom_id.as_str() {
-            "select_menu" => {
-                if let ComponentInteractionDataKind::StringSelect { values } =
-                    interaction.data.kind.clone()
-                {
-                    for value in &values {
-                        *selection.get_mut(value.as_str()).unwrap() = true;
-                    }
-
-                    if !last_selected.is_empty() {
-                        for value in &last_selected {
-                            if !values.contains(value) {
-                                *selection.get_mut(value.as_str()).unwrap() = false;
-                            }
-                        }
-                    }
-
-                    last_selected = values.clone();
-                    selected = selection
-                        .iter()
-                        .filter(|(_, set)| **set)
-                        .map(|(k, _)| k.to_string())
-                        .collect();
-                }
-            }
-            button_selection @ ("next" | "prev") => {
-                if button_selection == "next" {
-                    menu_index = (menu_index + 1).min(adjectives.len() / 25);
-                } else {
-                    menu_index = menu_index.saturating_sub(1);
-                }
-                last_selected.clear();
-            }
-            "submit" => {
-                interaction
-                    .create_response(
-                        &ctx.http,
-                        CreateInteractionResponse::UpdateMessage(
-                            CreateInteractionResponseMessage::new()
-                                .embed(
-                                    CreateEmbed::new()
-                                        .title("Submission recorded")
-                                        .description("Your submission has been recorded"),
-                                )
-                                .components(Vec::new()),
-                        ),
-                    )
-                    .await
-                    .unwrap();
-
-                collector.stop();
-                break;
-            }
-
-            _ => {}
-        }
-        interaction
-            .create_response(
-                &ctx.http,
-                CreateInteractionResponse::UpdateMessage(
-                    CreateInteractionResponseMessage::default()
-                        .embed(embed(&selected))
-                        .components(vec![
-                            select_menu(menu_index, &selected).clone(),
-                            buttons(&selected),
-                        ]),
-                ),
-            )
-            .await
-            .unwrap();
+pub async fn respond_autocomplete(
+    http: impl AsRef<Http>,
+    interaction: &CommandInteraction,
+    choices: Vec<(String, String)>,
+) {
+    let response = choices.into_iter().fold(
+        CreateAutocompleteResponse::new(),
+        |response, (name, value)| response.add_string_choice(name, value),
+    );
+    // Suggestions are dropped once the user typed further, which is not worth a panic.
+    if let Err(error) = interaction
+        .create_response(http, CreateInteractionResponse::Autocomplete(response))
+        .await
+    {
+        eprintln!("Cannot respond to autocomplete: {error}");
     }
+}
 
-    selected
+/// Responds to `interaction` with an error embed, translated for the interaction's locale
+/// when `message` is in its catalog and shown like the response it stands in for.
+pub async fn respond_embed_error(
+    http: impl AsRef<Http>,
+    interaction: impl Invocation,
+    message: &str,
+    visibility: Visibility,
+) {
+    let embed = CreateEmbed::new()
+        .title(locale::tr(interaction.locale(), "Error"))
+        .color((255, 0, 0))
+        .description(locale::tr(interaction.locale(), message));
+    respond_embed(http, &interaction, embed, visibility).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_user_options() {
+        assert_eq!(parse_user("1234"), Some(UserId::new(1234)));
+        assert_eq!(parse_user("<@1234>"), Some(UserId::new(1234)));
+        assert_eq!(parse_user("<@!1234>"), Some(UserId::new(1234)));
+        assert_eq!(parse_user("0"), None);
+        assert_eq!(parse_user("someone"), None);
+    }
 }
@@ -0,0 +1,43 @@
+use soshari_macros::adjectives;
+
+use super::engine::{impl_window, window_command, Kind, Texts, Window};
+
+#[adjectives(
+    adventure, ambition, authenticity, balance, community, compassion, creativity, curiosity,
+    discipline, equality, fairness, family, freedom, friendship, generosity, growth, harmony,
+    honesty, humility, humour, independence, justice, knowledge, leadership, loyalty, optimism,
+    recognition, respect, responsibility, security, simplicity, stability, tradition, wisdom
+)]
+pub type Values = Window<ValuesAdjectives>;
+
+impl_window!(ValuesAdjectives, ValuesGroup);
+
+impl Kind for ValuesAdjectives {
+    const STORE: &'static str = STORE;
+    const COLOR: (u8, u8, u8) = (0x5C, 0xD6, 0x8A);
+    const MIN: usize = 3;
+    const MAX: Option<usize> = Some(5);
+    const TEXTS: Texts = Texts {
+        test: "The Values window test",
+        about: "The Values window maps which values drive someone, as seen by themselves and by their peers",
+        footer: None,
+        window: "Values window",
+        overall: "The overall values window",
+        unreadable: "The values database could not be read",
+        missing_target: "Cannot find user in the values database",
+        missing_user: "Cannot find the user in the values database",
+        missing_own: "You are not in the values database yet",
+        missing_team: "No member with this role is in the values database",
+        similar: "Similar values windows",
+        stats: "Values statistics",
+        team: "Team values window",
+        team_members: "{} of {} members have a values window",
+        changes: "Values window changes",
+        history: "Values history",
+    };
+}
+
+window_command!(ValuesCommand, ValuesAdjectives, "values", "The values window test", "Der Werte-Fenster-Test");
+
+pub const STORE: &str = "values";
+
@@ -0,0 +1,176 @@
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::locale;
+
+/// Discord's limit on embed descriptions.
+const MAX_LENGTH: usize = 4096;
+const DAY: u64 = 24 * 60 * 60;
+
+/// A superseded submission, kept when a window or contribution is submitted again.
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct Revision<A> {
+    pub timestamp: u64,
+    pub adjectives: A,
+}
+
+/// A single change in a window's timeline.
+pub struct Entry {
+    pub timestamp: u64,
+    pub text: String,
+}
+
+/// Describes a sequence of submissions, oldest first, as the adjectives each one added
+/// and removed. `subject` names whose submissions these are, e.g. `Self-assessment`,
+/// untranslated.
+pub fn timeline(locale: &str, subject: &str, submissions: &[(u64, Vec<&'static str>)]) -> Vec<Entry> {
+    let mut entries = Vec::new();
+    let mut previous: &[&str] = &[];
+
+    for (timestamp, labels) in submissions {
+        let added = labels.iter().filter(|label| !previous.contains(label));
+        let removed = previous.iter().filter(|label| !labels.contains(label));
+        let text = if previous.is_empty() {
+            let labels = labels.iter().map(|label| locale::adjective(locale, label)).join(", ");
+            format!("{}: {}", locale::tr(locale, subject), labels)
+        } else {
+            let changes = added
+                .map(|label| format!("+{}", locale::adjective(locale, label)))
+                .chain(removed.map(|label| format!("-{}", locale::adjective(locale, label))))
+                .join(", ");
+            if changes.is_empty() {
+                locale::format(locale, "{} resubmitted unchanged", &[&locale::tr(locale, subject)])
+            } else {
+                locale::format(locale, "{} changed: {}", &[&locale::tr(locale, subject), &changes])
+            }
+        };
+
+        entries.push(Entry {
+            timestamp: *timestamp,
+            text,
+        });
+        previous = labels;
+    }
+
+    entries
+}
+
+/// Renders the latest `limit` entries that fit in an embed description, oldest first.
+/// Submissions made before timestamps were recorded have a timestamp of 0.
+pub fn render(locale: &str, mut entries: Vec<Entry>, limit: usize) -> String {
+    if entries.is_empty() {
+        return locale::tr(locale, "No submissions yet").to_string();
+    }
+
+    entries.sort_by_key(|entry| entry.timestamp);
+    let mut length = 0;
+    let lines = entries
+        .iter()
+        .rev()
+        .take(limit)
+        .map(|entry| match entry.timestamp {
+            0 => format!("{} - {}", locale::tr(locale, "Before history was recorded"), entry.text),
+            timestamp => format!("<t:{timestamp}:f> - {}", entry.text),
+        })
+        .take_while(|line| {
+            length += line.chars().count() + 1;
+            length <= MAX_LENGTH
+        })
+        .collect::<Vec<_>>();
+    lines.into_iter().rev().join("\n")
+}
+
+/// Days from 1970-01-01 to the given date of the proleptic gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Parses the `since` option of a diff, either a `YYYY-MM-DD` date (midnight UTC) or a
+/// duration before `now` such as `30d`, `2w` or `12h`.
+pub fn parse_since(since: &str, now: u64) -> Option<u64> {
+    let since = since.trim();
+
+    if let Some((count, unit)) = since
+        .find(|c: char| !c.is_ascii_digit())
+        .filter(|&index| index > 0)
+        .map(|index| since.split_at(index))
+    {
+        let seconds = match unit {
+            "h" => 60 * 60,
+            "d" => DAY,
+            "w" => 7 * DAY,
+            _ => return date(since),
+        };
+        return Some(now.saturating_sub(count.parse::<u64>().ok()?.checked_mul(seconds)?));
+    }
+
+    date(since)
+}
+
+fn date(date: &str) -> Option<u64> {
+    let mut parts = date.splitn(3, '-').map(str::parse::<i64>);
+    let (year, month, day) = (
+        parts.next()?.ok()?,
+        parts.next()?.ok()?,
+        parts.next()?.ok()?,
+    );
+
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return None,
+    };
+    if !(1..=days_in_month).contains(&day) {
+        return None;
+    }
+
+    u64::try_from(days_from_civil(year, month, day))
+        .ok()?
+        .checked_mul(DAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_durations() {
+        let now = 100 * DAY;
+        assert_eq!(parse_since("30d", now), Some(70 * DAY));
+        assert_eq!(parse_since("2w", now), Some(86 * DAY));
+        assert_eq!(parse_since("12h", now), Some(100 * DAY - 12 * 60 * 60));
+        assert_eq!(parse_since("1000d", now), Some(0));
+    }
+
+    #[test]
+    fn parses_dates() {
+        assert_eq!(parse_since("1970-01-01", 0), Some(0));
+        assert_eq!(parse_since("2024-02-29", 0), Some(1_709_164_800));
+        assert_eq!(parse_since("2023-02-29", 0), None);
+        assert_eq!(parse_since("2024-13-01", 0), None);
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert_eq!(parse_since("", 0), None);
+        assert_eq!(parse_since("d", 0), None);
+        assert_eq!(parse_since("30x", 0), None);
+        assert_eq!(parse_since("yesterday", 0), None);
+    }
+
+    #[test]
+    fn translates_adjectives() {
+        let submissions = [(1, vec!["bold"]), (2, vec!["bold", "brave"])];
+        let entries = timeline("de", "Self-assessment", &submissions);
+        assert!(entries[0].text.ends_with(": kühn"));
+        assert!(entries[1].text.ends_with(": +mutig"));
+    }
+}
@@ -0,0 +1,666 @@
+use std::fmt;
+
+/// The locale every text is written in, ending every fallback chain.
+pub const DEFAULT: &str = "en-US";
+
+/// Translations for a Discord locale, keyed by their English text.
+struct Catalog {
+    locale: &'static str,
+    texts: &'static [(&'static str, &'static str)],
+    /// Labels of the built-in adjectives.
+    adjectives: &'static [(&'static str, &'static str)],
+}
+
+const CATALOGS: &[Catalog] = &[Catalog {
+    locale: "de",
+    texts: &[
+        // Menus
+        ("Selected", "Ausgewählt"),
+        ("Nothing selected yet", "Noch nichts ausgewählt"),
+        ("Prev", "Zurück"),
+        ("Next", "Weiter"),
+        ("Submit", "Absenden"),
+        (
+            "Pick the adjectives that describe the person best",
+            "Wähle die Adjektive, die die Person am besten beschreiben",
+        ),
+        ("Submission recorded", "Auswahl gespeichert"),
+        ("Add a comment", "Kommentar hinzufügen"),
+        ("Comment", "Kommentar"),
+        (
+            "A short note, shown to them without your name",
+            "Eine kurze Notiz, die ohne deinen Namen angezeigt wird",
+        ),
+        ("Comments", "Kommentare"),
+        ("No comments yet", "Noch keine Kommentare"),
+        (
+            "This picker has expired, please start again",
+            "Diese Auswahl ist abgelaufen, bitte beginne erneut",
+        ),
+        (
+            "The picker sessions could not be read",
+            "Die offenen Auswahlen konnten nicht gelesen werden",
+        ),
+        ("Your submission has been recorded", "Deine Auswahl wurde gespeichert"),
+        ("Unimpressed by everything", "Von nichts beeindruckt"),
+        ("Helps others grow in their role", "Hilft anderen, in ihrer Rolle zu wachsen"),
+        ("Presents clearly in front of a group", "Präsentiert klar vor einer Gruppe"),
+        // Panel
+        ("Soshari windows", "Soshari-Fenster"),
+        (
+            "Take a window test or view your own johari window",
+            "Mache einen Fenstertest oder sieh dir dein eigenes Johari-Fenster an",
+        ),
+        ("Take Johari", "Johari machen"),
+        ("Take Nohari", "Nohari machen"),
+        ("View my window", "Mein Fenster ansehen"),
+        ("Panel posted", "Panel gepostet"),
+        (
+            "The panel has been posted in this channel",
+            "Das Panel wurde in diesem Kanal gepostet",
+        ),
+        // Windows
+        ("The Johari window test", "Der Johari-Fenster-Test"),
+        (
+            "The Johari Window was invented by Joseph Luft and Harrington Ingham in the 1950s as a model for mapping personality awareness",
+            "Das Johari-Fenster wurde in den 1950er-Jahren von Joseph Luft und Harrington Ingham als Modell der Selbst- und Fremdwahrnehmung entwickelt",
+        ),
+        ("The nohari window test", "Der Nohari-Fenster-Test"),
+        (
+            "The Nohari is a darker version of the Johari Window, invented by Joseph Luft and Harrington Ingham in the 1950s as a model for mapping personality awareness",
+            "Das Nohari-Fenster ist eine dunklere Version des Johari-Fensters, das in den 1950er-Jahren von Joseph Luft und Harrington Ingham als Modell der Selbst- und Fremdwahrnehmung entwickelt wurde",
+        ),
+        (
+            "This johari window is modified; see the original at https://kevan.org/johari",
+            "Dieses Fenster ist abgewandelt; das Original gibt es unter https://kevan.org/johari",
+        ),
+        (
+            "This nohari window is modified; see the original at https://kevan.org/nohari",
+            "Dieses Fenster ist abgewandelt; das Original gibt es unter https://kevan.org/nohari",
+        ),
+        ("The Competency window test", "Der Kompetenz-Fenster-Test"),
+        (
+            "The Competency window maps how someone's workplace strengths are seen by themselves and by their colleagues",
+            "Das Kompetenz-Fenster zeigt, wie die beruflichen Stärken einer Person von ihr selbst und von ihren Kollegen gesehen werden",
+        ),
+        ("The Values window test", "Der Werte-Fenster-Test"),
+        (
+            "The Values window maps which values drive someone, as seen by themselves and by their peers",
+            "Das Werte-Fenster zeigt, welche Werte eine Person antreiben, aus ihrer eigenen Sicht und aus der ihrer Mitmenschen",
+        ),
+        ("Johari window", "Johari-Fenster"),
+        ("Nohari window", "Nohari-Fenster"),
+        ("Competency window", "Kompetenz-Fenster"),
+        ("Values window", "Werte-Fenster"),
+        ("The overall johari window", "Das gesamte Johari-Fenster"),
+        ("The overall nohari window", "Das gesamte Nohari-Fenster"),
+        ("The overall competency window", "Das gesamte Kompetenz-Fenster"),
+        ("The overall values window", "Das gesamte Werte-Fenster"),
+        ("Arena", "Öffentlich"),
+        ("Blind", "Blinder Fleck"),
+        ("Facade", "Fassade"),
+        ("Unknown", "Unbekannt"),
+        ("Self-awareness", "Selbstwahrnehmung"),
+        (
+            "overlap with the peers' combined view",
+            "Übereinstimmung mit der gemeinsamen Sicht der anderen",
+        ),
+        (
+            "average agreement with each peer",
+            "durchschnittliche Übereinstimmung mit jeder Person",
+        ),
+        ("Similar johari windows", "Ähnliche Johari-Fenster"),
+        ("Similar nohari windows", "Ähnliche Nohari-Fenster"),
+        ("Similar competency windows", "Ähnliche Kompetenz-Fenster"),
+        ("Similar values windows", "Ähnliche Werte-Fenster"),
+        (
+            "Nobody in this server has a comparable window yet",
+            "Auf diesem Server hat noch niemand ein vergleichbares Fenster",
+        ),
+        ("Discoverability updated", "Auffindbarkeit aktualisiert"),
+        (
+            "You appear in the similar rankings of this server",
+            "Du erscheinst in den Ähnlichkeits-Ranglisten dieses Servers",
+        ),
+        (
+            "You no longer appear in the similar rankings of this server",
+            "Du erscheinst nicht mehr in den Ähnlichkeits-Ranglisten dieses Servers",
+        ),
+        ("Privacy updated", "Privatsphäre aktualisiert"),
+        (
+            "Your queries in this server are only shown to you unless you ask otherwise",
+            "Deine Abfragen auf diesem Server werden nur dir angezeigt, außer du wählst es anders",
+        ),
+        (
+            "Your queries in this server are shown to the channel unless you ask otherwise",
+            "Deine Abfragen auf diesem Server werden im Kanal angezeigt, außer du wählst es anders",
+        ),
+        ("Johari statistics", "Johari-Statistiken"),
+        ("Nohari statistics", "Nohari-Statistiken"),
+        ("Competency statistics", "Kompetenz-Statistiken"),
+        ("Values statistics", "Werte-Statistiken"),
+        ("{} windows with {} peer contributions", "{} Fenster mit {} Beiträgen anderer"),
+        ("Most chosen by users", "Am häufigsten selbst gewählt"),
+        ("Least chosen by users", "Am seltensten selbst gewählt"),
+        ("Most chosen by peers", "Am häufigsten von anderen gewählt"),
+        ("Least chosen by peers", "Am seltensten von anderen gewählt"),
+        ("Self/peer agreement", "Übereinstimmung von Selbst- und Fremdbild"),
+        (
+            "{}% of peer choices match the self-assessment",
+            "{} % der Auswahlen anderer stimmen mit der Selbsteinschätzung überein",
+        ),
+        ("Top contributors", "Häufigste Beitragende"),
+        ("N/A", "k. A."),
+        ("Team johari window", "Team-Johari-Fenster"),
+        ("Team nohari window", "Team-Nohari-Fenster"),
+        ("Team competency window", "Team-Kompetenz-Fenster"),
+        ("Team values window", "Team-Werte-Fenster"),
+        ("{} of {} members have a johari window", "{} von {} Mitgliedern haben ein Johari-Fenster"),
+        ("{} of {} members have a nohari window", "{} von {} Mitgliedern haben ein Nohari-Fenster"),
+        (
+            "{} of {} members have a competency window",
+            "{} von {} Mitgliedern haben ein Kompetenz-Fenster",
+        ),
+        ("{} of {} members have a values window", "{} von {} Mitgliedern haben ein Werte-Fenster"),
+        ("Johari window changes", "Änderungen am Johari-Fenster"),
+        ("Nohari window changes", "Änderungen am Nohari-Fenster"),
+        ("Competency window changes", "Änderungen am Kompetenz-Fenster"),
+        ("Values window changes", "Änderungen am Werte-Fenster"),
+        ("{} since {}", "{} seit {}"),
+        (
+            "No adjectives moved between quadrants",
+            "Kein Adjektiv hat den Quadranten gewechselt",
+        ),
+        ("Johari history", "Johari-Verlauf"),
+        ("Nohari history", "Nohari-Verlauf"),
+        ("Competency history", "Kompetenz-Verlauf"),
+        ("Values history", "Werte-Verlauf"),
+        ("Self-assessment", "Selbsteinschätzung"),
+        ("Peer contribution", "Beitrag anderer"),
+        ("{} resubmitted unchanged", "{} unverändert erneut abgegeben"),
+        ("{} changed: {}", "{} geändert: {}"),
+        ("No submissions yet", "Noch keine Abgaben"),
+        ("Before history was recorded", "Vor Beginn der Aufzeichnung"),
+        ("The {} window test", "Der {}-Fenster-Test"),
+        ("{} window", "{}-Fenster"),
+        ("The overall {} window of {}", "Das gesamte {}-Fenster von {}"),
+        // Errors
+        ("Error", "Fehler"),
+        (
+            "The program reached a (supposedly) unreachable state. Something went wrong",
+            "Das Programm hat einen (angeblich) unerreichbaren Zustand erreicht. Etwas ist schiefgelaufen",
+        ),
+        ("You cannot contribute to yourself!", "Du kannst nicht zu dir selbst beitragen!"),
+        (
+            "Unknown server, pick one of the suggestions",
+            "Unbekannter Server, wähle einen der Vorschläge",
+        ),
+        ("You are not a member of that server", "Du bist kein Mitglied dieses Servers"),
+        (
+            "Unknown user, pick one of the suggestions",
+            "Unbekannter Nutzer, wähle einen der Vorschläge",
+        ),
+        (
+            "Cannot find the user this command was used on",
+            "Der Nutzer, auf den dieser Befehl angewendet wurde, wurde nicht gefunden",
+        ),
+        (
+            "You are banned from contributing in this server",
+            "Du bist auf diesem Server vom Beitragen ausgeschlossen",
+        ),
+        ("The guild settings could not be read", "Die Servereinstellungen konnten nicht gelesen werden"),
+        (
+            "The guild settings could not be saved",
+            "Die Servereinstellungen konnten nicht gespeichert werden",
+        ),
+        ("Cannot list the members of this server", "Die Mitglieder dieses Servers können nicht aufgelistet werden"),
+        ("Unknown view, expected self or peers", "Unbekannte Ansicht, erwartet wird self oder peers"),
+        ("Rankings can only be shown in a server", "Ranglisten können nur auf einem Server angezeigt werden"),
+        ("Rankings are only shown in a server", "Ranglisten werden nur auf einem Server angezeigt"),
+        ("Privacy can only be set in a server", "Die Privatsphäre kann nur auf einem Server eingestellt werden"),
+        ("Statistics can only be shown in a server", "Statistiken können nur auf einem Server angezeigt werden"),
+        ("Team windows can only be shown in a server", "Team-Fenster können nur auf einem Server angezeigt werden"),
+        (
+            "Expected a date like 2024-01-31 or a duration like 30d",
+            "Erwartet wird ein Datum wie 2024-01-31 oder eine Dauer wie 30d",
+        ),
+        ("The johari database could not be read", "Die Johari-Datenbank konnte nicht gelesen werden"),
+        ("The nohari database could not be read", "Die Nohari-Datenbank konnte nicht gelesen werden"),
+        ("The competency database could not be read", "Die Kompetenz-Datenbank konnte nicht gelesen werden"),
+        ("The values database could not be read", "Die Werte-Datenbank konnte nicht gelesen werden"),
+        ("Cannot find user in the johari database", "Der Nutzer ist nicht in der Johari-Datenbank"),
+        ("Cannot find user in the nohari database", "Der Nutzer ist nicht in der Nohari-Datenbank"),
+        ("Cannot find user in the competency database", "Der Nutzer ist nicht in der Kompetenz-Datenbank"),
+        ("Cannot find user in the values database", "Der Nutzer ist nicht in der Werte-Datenbank"),
+        ("Cannot find the user in the johari database", "Der Nutzer ist nicht in der Johari-Datenbank"),
+        ("Cannot find the user in the nohari database", "Der Nutzer ist nicht in der Nohari-Datenbank"),
+        ("Cannot find the user in the competency database", "Der Nutzer ist nicht in der Kompetenz-Datenbank"),
+        ("Cannot find the user in the values database", "Der Nutzer ist nicht in der Werte-Datenbank"),
+        ("You are not in the johari database yet", "Du bist noch nicht in der Johari-Datenbank"),
+        ("You are not in the nohari database yet", "Du bist noch nicht in der Nohari-Datenbank"),
+        ("You are not in the competency database yet", "Du bist noch nicht in der Kompetenz-Datenbank"),
+        ("You are not in the values database yet", "Du bist noch nicht in der Werte-Datenbank"),
+        (
+            "No member with this role is in the johari database",
+            "Kein Mitglied mit dieser Rolle ist in der Johari-Datenbank",
+        ),
+        (
+            "No member with this role is in the nohari database",
+            "Kein Mitglied mit dieser Rolle ist in der Nohari-Datenbank",
+        ),
+        (
+            "No member with this role is in the competency database",
+            "Kein Mitglied mit dieser Rolle ist in der Kompetenz-Datenbank",
+        ),
+        (
+            "No member with this role is in the values database",
+            "Kein Mitglied mit dieser Rolle ist in der Werte-Datenbank",
+        ),
+        ("Custom windows can only be used in a server", "Eigene Fenster können nur auf einem Server verwendet werden"),
+        ("Cannot find the adjective set", "Die Adjektivliste wurde nicht gefunden"),
+        ("Cannot find the user in the adjective set", "Der Nutzer ist nicht in der Adjektivliste"),
+        ("The adjective sets could not be read", "Die Adjektivlisten konnten nicht gelesen werden"),
+        ("The adjective sets could not be saved", "Die Adjektivlisten konnten nicht gespeichert werden"),
+        (
+            "Adjective sets can only be managed in a server",
+            "Adjektivlisten können nur auf einem Server verwaltet werden",
+        ),
+        (
+            "You need the Manage Server permission to manage adjective sets",
+            "Du brauchst die Berechtigung „Server verwalten“, um Adjektivlisten zu verwalten",
+        ),
+        ("Admin commands can only be used in a server", "Admin-Befehle können nur auf einem Server verwendet werden"),
+        (
+            "You need the Manage Server permission to moderate window data",
+            "Du brauchst die Berechtigung „Server verwalten“, um Fensterdaten zu moderieren",
+        ),
+        ("The user is not a member of this server", "Der Nutzer ist kein Mitglied dieses Servers"),
+        (
+            "Unknown window type, expected johari, nohari, competency, values or an adjective set",
+            "Unbekannter Fenstertyp, erwartet wird johari, nohari, competency, values oder eine Adjektivliste",
+        ),
+        ("Expected a single unicode emoji", "Erwartet wird ein einzelnes Unicode-Emoji"),
+        (
+            "This set has reached the maximum number of adjectives",
+            "Diese Liste hat die Höchstzahl an Adjektiven erreicht",
+        ),
+        ("The user has no window", "Der Nutzer hat kein Fenster"),
+        ("Cannot find the contribution", "Der Beitrag wurde nicht gefunden"),
+        ("The window database could not be read", "Die Fensterdatenbank konnte nicht gelesen werden"),
+        (
+            "The window database could not be updated",
+            "Die Fensterdatenbank konnte nicht aktualisiert werden",
+        ),
+        ("The audit log could not be read", "Das Prüfprotokoll konnte nicht gelesen werden"),
+        (
+            "Only the bot owner can manage window data",
+            "Nur der Besitzer des Bots kann Fensterdaten verwalten",
+        ),
+        ("Cannot list snapshots", "Die Sicherungen können nicht aufgelistet werden"),
+        ("Cannot find the snapshot", "Die Sicherung wurde nicht gefunden"),
+        ("Unknown snapshot store", "Unbekannter Speicher der Sicherung"),
+        ("Cannot restore the snapshot", "Die Sicherung kann nicht wiederhergestellt werden"),
+        (
+            "The panel can only be posted in a server",
+            "Das Panel kann nur auf einem Server gepostet werden",
+        ),
+        (
+            "You need the Manage Server permission to post a panel",
+            "Du brauchst die Berechtigung „Server verwalten“, um ein Panel zu posten",
+        ),
+        (
+            "The panel could not be posted in this channel",
+            "Das Panel konnte nicht in diesem Kanal gepostet werden",
+        ),
+        // Moderation
+        ("Adjective sets", "Adjektivlisten"),
+        ("This server has no adjective sets yet", "Dieser Server hat noch keine Adjektivlisten"),
+        ("The {} adjective set", "Die Adjektivliste {}"),
+        ("Contributors", "Beitragende"),
+        ("Audit log", "Prüfprotokoll"),
+        ("No events have been recorded yet", "Es wurden noch keine Ereignisse aufgezeichnet"),
+        ("By {}", "Von {}"),
+        ("Self-assessment updated", "Selbsteinschätzung aktualisiert"),
+        ("Contribution added", "Beitrag hinzugefügt"),
+        ("Contribution removed", "Beitrag entfernt"),
+        ("Contributions listed", "Beiträge aufgelistet"),
+        ("Window wiped", "Fenster gelöscht"),
+        ("User banned", "Nutzer ausgeschlossen"),
+        ("User unbanned", "Ausschluss aufgehoben"),
+        ("Log channel set", "Protokollkanal festgelegt"),
+        ("Adjective set updated", "Adjektivliste aktualisiert"),
+        ("Submitted a {} self-assessment", "Hat eine {}-Selbsteinschätzung abgegeben"),
+        ("Contributed to the {} window of {}", "Hat zum {}-Fenster von {} beigetragen"),
+        (
+            "Removed the contribution of {} to the {} window of {}",
+            "Hat den Beitrag von {} zum {}-Fenster von {} entfernt",
+        ),
+        (
+            "Listed the contributions to the {} window of {}",
+            "Hat die Beiträge zum {}-Fenster von {} aufgelistet",
+        ),
+        ("Deleted the {} window of {}", "Hat das {}-Fenster von {} gelöscht"),
+        ("{} can no longer contribute to windows", "{} kann nicht mehr zu Fenstern beitragen"),
+        ("{} can contribute to windows again", "{} kann wieder zu Fenstern beitragen"),
+        ("Window activity is logged to {}", "Fensteraktivität wird in {} protokolliert"),
+        ("Adjective set {}: {}", "Adjektivliste {}: {}"),
+        ("Snapshots", "Sicherungen"),
+        ("No snapshots have been taken yet", "Es wurden noch keine Sicherungen erstellt"),
+        ("Restore preview", "Vorschau der Wiederherstellung"),
+        ("Snapshot restored", "Sicherung wiederhergestellt"),
+        ("{} from {}", "{} vom {}"),
+        ("The snapshot matches the current data", "Die Sicherung entspricht den aktuellen Daten"),
+        (
+            "Only server managers can view the changes to another user's window",
+            "Nur Serververwalter können die Änderungen am Fenster anderer Nutzer sehen",
+        ),
+        ("{} peer contributions", "{} Beiträge von anderen"),
+        (
+            "This window type is shared by every server, set confirm to delete its data everywhere",
+            "Dieser Fenstertyp wird von allen Servern geteilt, setze confirm, um seine Daten überall zu löschen",
+        ),
+        (
+            "Pick the server to contribute from with the guild option",
+            "Wähle mit der Option guild den Server, von dem aus du beiträgst",
+        ),
+        (
+            "Contributions to this window are anonymous",
+            "Beiträge zu diesem Fenster sind anonym",
+        ),
+        ("Comments are disabled in this server", "Kommentare sind auf diesem Server deaktiviert"),
+        ("Comment numbers start at 1", "Kommentare werden ab 1 gezählt"),
+        ("Cannot find the comment", "Der Kommentar wurde nicht gefunden"),
+        ("Comment left", "Kommentar hinterlassen"),
+        ("Comments removed", "Kommentare entfernt"),
+        ("Comment setting changed", "Kommentareinstellung geändert"),
+        (
+            "A comment was left on the {} window of {}",
+            "Zum {}-Fenster von {} wurde ein Kommentar hinterlassen",
+        ),
+        (
+            "Removed comments from the {} window of {} ({})",
+            "Hat Kommentare vom {}-Fenster von {} entfernt ({})",
+        ),
+        (
+            "Contributions from this server may carry comments",
+            "Beiträge von diesem Server dürfen Kommentare enthalten",
+        ),
+        (
+            "Contributions from this server no longer carry comments",
+            "Beiträge von diesem Server enthalten keine Kommentare mehr",
+        ),
+        ("Added", "Hinzugefügt"),
+        ("Removed", "Entfernt"),
+        ("Changed", "Geändert"),
+    ],
+    adjectives: &[
+        // Johari
+        ("accepting", "akzeptierend"),
+        ("adaptable", "anpassungsfähig"),
+        ("bold", "kühn"),
+        ("brave", "mutig"),
+        ("calm", "ruhig"),
+        ("caring", "fürsorglich"),
+        ("cheerful", "fröhlich"),
+        ("confident", "selbstsicher"),
+        ("dependable", "verlässlich"),
+        ("dignified", "würdevoll"),
+        ("energetic", "energisch"),
+        ("extroverted", "extrovertiert"),
+        ("friendly", "freundlich"),
+        ("giving", "gebend"),
+        ("happy", "glücklich"),
+        ("helpful", "hilfsbereit"),
+        ("idealistic", "idealistisch"),
+        ("independent", "unabhängig"),
+        ("ingenious", "genial"),
+        ("intelligent", "intelligent"),
+        ("introverted", "introvertiert"),
+        ("kind", "gütig"),
+        ("knowledgeable", "kenntnisreich"),
+        ("logical", "logisch"),
+        ("loving", "liebevoll"),
+        ("mature", "reif"),
+        ("modest", "bescheiden"),
+        ("nervous", "nervös"),
+        ("observant", "aufmerksam"),
+        ("organised", "organisiert"),
+        ("patient", "geduldig"),
+        ("proud", "stolz"),
+        ("quiet", "still"),
+        ("reflective", "nachdenklich"),
+        ("relaxed", "entspannt"),
+        ("responsive", "empfänglich"),
+        ("self-assertive", "durchsetzungsfähig"),
+        ("self-conscious", "befangen"),
+        ("sensible", "vernünftig"),
+        ("sentimental", "sentimental"),
+        ("shy", "schüchtern"),
+        ("silly", "albern"),
+        ("spontaneous", "spontan"),
+        ("sympathetic", "mitfühlend"),
+        ("tense", "angespannt"),
+        ("trustworthy", "vertrauenswürdig"),
+        ("warm", "warmherzig"),
+        ("witty", "geistreich"),
+        ("wise", "weise"),
+        // Nohari
+        ("incompetent", "inkompetent"),
+        ("intolerant", "intolerant"),
+        ("inflexible", "unflexibel"),
+        ("timid", "ängstlich"),
+        ("cowardly", "feige"),
+        ("violent", "gewalttätig"),
+        ("aloof", "distanziert"),
+        ("glum", "missmutig"),
+        ("stupid", "dumm"),
+        ("simple", "einfältig"),
+        ("insecure", "unsicher"),
+        ("irresponsible", "verantwortungslos"),
+        ("vulgar", "vulgär"),
+        ("lethargic", "lethargisch"),
+        ("withdrawn", "verschlossen"),
+        ("hostile", "feindselig"),
+        ("selfish", "egoistisch"),
+        ("unhappy", "unglücklich"),
+        ("unhelpful", "wenig hilfsbereit"),
+        ("cynical", "zynisch"),
+        ("needy", "bedürftig"),
+        ("unimaginative", "fantasielos"),
+        ("inane", "geistlos"),
+        ("brash", "dreist"),
+        ("cruel", "grausam"),
+        ("ignorant", "ignorant"),
+        ("irrational", "irrational"),
+        ("distant", "unnahbar"),
+        ("childish", "kindisch"),
+        ("boastful", "prahlerisch"),
+        ("blasé", "blasiert"),
+        ("imperceptive", "unaufmerksam"),
+        ("chaotic", "chaotisch"),
+        ("impatient", "ungeduldig"),
+        ("weak", "schwach"),
+        ("embarrassed", "verlegen"),
+        ("loud", "laut"),
+        ("vacuous", "hohl"),
+        ("panicky", "panisch"),
+        ("unethical", "unethisch"),
+        ("insensitive", "unsensibel"),
+        ("self-satisfied", "selbstzufrieden"),
+        ("passive", "passiv"),
+        ("smug", "selbstgefällig"),
+        ("rash", "voreilig"),
+        ("dispassionate", "leidenschaftslos"),
+        ("overdramatic", "überdramatisch"),
+        ("dull", "langweilig"),
+        ("predictable", "vorhersehbar"),
+        ("callous", "gefühllos"),
+        ("inattentive", "unachtsam"),
+        ("unreliable", "unzuverlässig"),
+        ("cold", "kalt"),
+        ("foolish", "töricht"),
+        ("humourless", "humorlos"),
+        // Competency
+        ("accountable", "verantwortungsbewusst"),
+        ("analytical", "analytisch"),
+        ("assertive", "bestimmt"),
+        ("collaborative", "kooperativ"),
+        ("communicative", "kommunikativ"),
+        ("creative", "kreativ"),
+        ("decisive", "entschlossen"),
+        ("delegating", "delegierend"),
+        ("detail-oriented", "detailorientiert"),
+        ("diplomatic", "diplomatisch"),
+        ("efficient", "effizient"),
+        ("flexible", "flexibel"),
+        ("focused", "fokussiert"),
+        ("goal-oriented", "zielorientiert"),
+        ("honest", "ehrlich"),
+        ("innovative", "innovativ"),
+        ("inspiring", "inspirierend"),
+        ("methodical", "methodisch"),
+        ("mentoring", "fördernd"),
+        ("motivated", "motiviert"),
+        ("negotiating", "verhandlungsstark"),
+        ("persuasive", "überzeugend"),
+        ("proactive", "proaktiv"),
+        ("public-speaking", "redegewandt"),
+        ("punctual", "pünktlich"),
+        ("reliable", "zuverlässig"),
+        ("resilient", "belastbar"),
+        ("resourceful", "einfallsreich"),
+        ("self-directed", "selbstständig"),
+        ("strategic", "strategisch"),
+        ("supportive", "unterstützend"),
+        ("tactful", "taktvoll"),
+        ("thorough", "gründlich"),
+        ("versatile", "vielseitig"),
+        // Values
+        ("adventure", "Abenteuer"),
+        ("ambition", "Ehrgeiz"),
+        ("authenticity", "Authentizität"),
+        ("balance", "Ausgeglichenheit"),
+        ("community", "Gemeinschaft"),
+        ("compassion", "Mitgefühl"),
+        ("creativity", "Kreativität"),
+        ("curiosity", "Neugier"),
+        ("discipline", "Disziplin"),
+        ("equality", "Gleichberechtigung"),
+        ("fairness", "Fairness"),
+        ("family", "Familie"),
+        ("freedom", "Freiheit"),
+        ("friendship", "Freundschaft"),
+        ("generosity", "Großzügigkeit"),
+        ("growth", "Wachstum"),
+        ("harmony", "Harmonie"),
+        ("honesty", "Ehrlichkeit"),
+        ("humility", "Demut"),
+        ("humour", "Humor"),
+        ("independence", "Unabhängigkeit"),
+        ("justice", "Gerechtigkeit"),
+        ("knowledge", "Wissen"),
+        ("leadership", "Führung"),
+        ("loyalty", "Loyalität"),
+        ("optimism", "Optimismus"),
+        ("recognition", "Anerkennung"),
+        ("respect", "Respekt"),
+        ("responsibility", "Verantwortung"),
+        ("security", "Sicherheit"),
+        ("simplicity", "Einfachheit"),
+        ("stability", "Stabilität"),
+        ("tradition", "Tradition"),
+        ("wisdom", "Weisheit"),
+    ],
+}];
+
+/// The locales tried for `locale`, most specific first: the locale itself, its language
+/// (`pt` for `pt-BR`) and [`DEFAULT`].
+pub fn fallbacks(locale: &str) -> Vec<&str> {
+    let mut chain = vec![locale];
+    if let Some((language, _)) = locale.split_once('-') {
+        chain.push(language);
+    }
+    if !chain.contains(&DEFAULT) {
+        chain.push(DEFAULT);
+    }
+    chain
+}
+
+fn lookup<'a>(
+    locale: &str,
+    key: &'a str,
+    entries: fn(&Catalog) -> &'static [(&'static str, &'static str)],
+) -> &'a str {
+    fallbacks(locale)
+        .into_iter()
+        .find_map(|locale| {
+            let catalog = CATALOGS.iter().find(|catalog| catalog.locale == locale)?;
+            entries(catalog)
+                .iter()
+                .find(|(english, _)| *english == key)
+                .map(|(_, translation)| *translation)
+        })
+        .unwrap_or(key)
+}
+
+/// `text` translated for `locale`, or `text` itself when no locale of the fallback
+/// chain translates it.
+pub fn tr<'a>(locale: &str, text: &'a str) -> &'a str {
+    lookup(locale, text, |catalog| catalog.texts)
+}
+
+/// `text` translated for `locale` with each `{}` placeholder replaced by the next of
+/// `args`, so translations may reorder the sentence around them.
+pub fn format(locale: &str, text: &str, args: &[&dyn fmt::Display]) -> String {
+    let mut args = args.iter();
+    let mut parts = tr(locale, text).split("{}");
+    let mut formatted = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        if let Some(arg) = args.next() {
+            formatted.push_str(&arg.to_string());
+        }
+        formatted.push_str(part);
+    }
+    formatted
+}
+
+/// The label of a built-in adjective translated for `locale`, or `label` itself for
+/// untranslated and custom adjectives.
+pub fn adjective<'a>(locale: &str, label: &'a str) -> &'a str {
+    lookup(locale, label, |catalog| catalog.adjectives)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn falls_back_to_language_then_default() {
+        assert_eq!(fallbacks("pt-BR"), vec!["pt-BR", "pt", "en-US"]);
+        assert_eq!(fallbacks("de"), vec!["de", "en-US"]);
+        assert_eq!(fallbacks("en-US"), vec!["en-US", "en"]);
+    }
+
+    #[test]
+    fn translates_known_texts_only() {
+        assert_eq!(tr("de", "Error"), "Fehler");
+        assert_eq!(tr("de-AT", "Error"), "Fehler");
+        assert_eq!(tr("en-GB", "Error"), "Error");
+        assert_eq!(tr("de", "Not a catalog entry"), "Not a catalog entry");
+        assert_eq!(adjective("de", "shy"), "schüchtern");
+        assert_eq!(adjective("fr", "shy"), "shy");
+    }
+
+    #[test]
+    fn formats_placeholders_in_order() {
+        assert_eq!(
+            format("en-US", "{} of {} members", &[&2, &3]),
+            "2 of 3 members"
+        );
+        assert_eq!(
+            format("de", "No comments yet", &[]),
+            "Noch keine Kommentare"
+        );
+        assert_eq!(format("en-US", "{} and {}", &[&1]), "1 and ");
+    }
+}
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use dotenv_codegen::dotenv;
 
 use serenity::{
@@ -7,19 +9,63 @@ use serenity::{
     Client,
 };
 
+mod audit;
 mod commands;
+mod history;
+mod locale;
+mod sets;
+mod settings;
+mod stats;
+mod store;
+mod window;
 
 struct Handler {}
 
 #[async_trait]
 impl EventHandler for Handler {
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
-        if let Interaction::Command(command) = interaction {
-            match command.data.name.as_str() {
+        match interaction {
+            Interaction::Command(command) => match command.data.name.as_str() {
                 "johari" => commands::johari::run(ctx, command).await,
                 "nohari" => commands::nohari::run(ctx, command).await,
+                "competency" => commands::competency::run(ctx, command).await,
+                "values" => commands::values::run(ctx, command).await,
+                "soshari" => commands::soshari::run(ctx, command).await,
+                "custom" => commands::custom::run(ctx, command).await,
+                commands::johari::DESCRIBE | commands::johari::VIEW => {
+                    commands::johari::run_user(ctx, command).await
+                }
+                _ => println!(":( Unimplemented"),
+            },
+            Interaction::Autocomplete(interaction) => match interaction.data.name.as_str() {
+                "johari" => commands::johari::autocomplete(ctx, interaction).await,
+                "nohari" => commands::nohari::autocomplete(ctx, interaction).await,
+                "competency" => commands::competency::autocomplete(ctx, interaction).await,
+                "values" => commands::values::autocomplete(ctx, interaction).await,
+                "soshari" => commands::soshari::autocomplete(ctx, interaction).await,
+                "custom" => commands::custom::autocomplete(ctx, interaction).await,
                 _ => println!(":( Unimplemented"),
+            },
+            Interaction::Component(component) => {
+                let custom_id = component.data.custom_id.as_str();
+                if custom_id.starts_with(commands::panel::PREFIX) {
+                    commands::panel::run(ctx, component).await
+                } else if custom_id.starts_with(commands::picker::PREFIX) {
+                    commands::picker::run(ctx, component).await
+                } else if custom_id.starts_with(commands::comments::PREFIX) {
+                    commands::comments::run(ctx, component).await
+                } else {
+                    println!(":( Unimplemented")
+                }
             }
+            Interaction::Modal(modal) => {
+                if modal.data.custom_id.starts_with(commands::picker::PREFIX) {
+                    commands::picker::run_modal(ctx, modal).await
+                } else {
+                    println!(":( Unimplemented")
+                }
+            }
+            _ => {}
         }
     }
 
@@ -32,15 +78,56 @@ impl EventHandler for Handler {
         Command::create_global_application_command(&ctx.http, commands::nohari::create())
             .await
             .expect("Error while creating new command");
+        Command::create_global_application_command(&ctx.http, commands::competency::create())
+            .await
+            .expect("Error while creating new command");
+        Command::create_global_application_command(&ctx.http, commands::values::create())
+            .await
+            .expect("Error while creating new command");
+        Command::create_global_application_command(&ctx.http, commands::soshari::create())
+            .await
+            .expect("Error while creating new command");
+        Command::create_global_application_command(&ctx.http, commands::custom::create())
+            .await
+            .expect("Error while creating new command");
+        for command in commands::johari::create_user() {
+            Command::create_global_application_command(&ctx.http, command)
+                .await
+                .expect("Error while creating new command");
+        }
     }
 }
 
 #[tokio::main]
 async fn main() {
-    let intents = GatewayIntents::empty() | GatewayIntents::GUILDS | GatewayIntents::GUILD_MEMBERS;
+    if let Err(error) = store::bootstrap(&[
+        commands::johari::STORE,
+        commands::nohari::STORE,
+        commands::competency::STORE,
+        commands::values::STORE,
+        settings::STORE,
+        audit::STORE,
+        sets::STORE,
+        commands::picker::STORE,
+    ]) {
+        eprintln!(
+            "Cannot use the data directory {}: {error}",
+            store::data_directory().display()
+        );
+        std::process::exit(1);
+    }
+
+    let intents = GatewayIntents::empty()
+        | GatewayIntents::GUILDS
+        | GatewayIntents::GUILD_MEMBERS
+        | GatewayIntents::DIRECT_MESSAGES;
     let handler = Handler {};
     let mut client = Client::builder(dotenv!("DISCORD_TOKEN"), intents)
         .event_handler(handler)
+        .type_map_insert::<commands::picker::SessionLock>(Arc::default())
+        .type_map_insert::<settings::SettingsLock>(Arc::default())
+        .type_map_insert::<audit::AuditLock>(Arc::default())
+        .type_map_insert::<sets::SetsLock>(Arc::default())
         .await
         .expect("Cannot create client");
 
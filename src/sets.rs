@@ -0,0 +1,512 @@
+use std::{fs, io, path::Path, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use serenity::{
+    model::prelude::{GuildId, UserId},
+    prelude::*,
+};
+
+use crate::{history::Revision, store};
+
+pub const STORE: &str = "sets";
+
+/// Serializes changes to the sets store across handlers.
+pub struct SetsLock;
+
+impl TypeMapKey for SetsLock {
+    type Value = Arc<Mutex<()>>;
+}
+
+/// Names of the built-in sets, which custom sets cannot take.
+const RESERVED: &[&str] = &["johari", "nohari", "competency", "values"];
+/// Discord's limit on select menu option labels and descriptions.
+const MAX_LENGTH: usize = 100;
+/// Most adjectives a set can hold, about double the largest built-in set (nohari with
+/// 55) so pickers stay usable.
+const MAX_ADJECTIVES: usize = 120;
+/// Version of the stored JSON, see [`Sets::parse`].
+const VERSION: u64 = 1;
+
+/// An adjective of a runtime-defined set, mirroring what `#[adjectives]` accepts.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SetAdjective {
+    pub name: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub emoji: Option<String>,
+}
+
+impl SetAdjective {
+    /// The name shown to users, defaulting to the name.
+    pub fn label(&self) -> &str {
+        self.label.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// A window over a runtime-defined set, storing adjectives by name.
+#[derive(Serialize, Deserialize)]
+pub struct Window {
+    pub id: u64,
+    pub adjectives: Vec<String>,
+    #[serde(default)]
+    pub timestamp: u64,
+    #[serde(default)]
+    pub history: Vec<Revision<Vec<String>>>,
+    #[serde(default)]
+    pub others: Vec<Window>,
+    /// The comment left with a contribution, shown without its author.
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+impl Window {
+    pub fn new(id: u64, adjectives: Vec<String>, comment: Option<String>) -> Self {
+        Self {
+            id,
+            adjectives,
+            timestamp: store::now(),
+            history: Vec::new(),
+            others: Vec::new(),
+            comment,
+        }
+    }
+
+    /// Replaces the submitted adjectives, keeping the previous submission in `history`.
+    /// The comment is only replaced when a new one is left.
+    pub fn revise(&mut self, adjectives: Vec<String>, comment: Option<String>) {
+        if comment.is_some() {
+            self.comment = comment;
+        }
+        let previous = std::mem::replace(&mut self.adjectives, adjectives);
+        self.history.push(Revision {
+            timestamp: self.timestamp,
+            adjectives: previous,
+        });
+        self.timestamp = store::now();
+    }
+
+    /// The comments left with the contributions to this window.
+    pub fn comments(&self) -> Vec<String> {
+        self.others
+            .iter()
+            .filter_map(|other| other.comment.clone())
+            .collect()
+    }
+
+    /// Clears comment `number` of [`Window::comments`], counting from 1, or every
+    /// comment without a `number`, returning how many were cleared.
+    pub fn remove_comments(&mut self, number: Option<usize>) -> usize {
+        self.others
+            .iter_mut()
+            .filter(|other| other.comment.is_some())
+            .enumerate()
+            .filter(|(index, _)| number.is_none_or(|number| number == index + 1))
+            .map(|(_, other)| other.comment = None)
+            .count()
+    }
+}
+
+/// A guild's own adjective vocabulary along with the windows taken with it.
+#[derive(Serialize, Deserialize)]
+pub struct AdjectiveSet {
+    pub guild_id: u64,
+    pub name: String,
+    pub adjectives: Vec<SetAdjective>,
+    /// Names removed from the set, which cannot be reused as existing windows may
+    /// still refer to them.
+    #[serde(default)]
+    pub retired: Vec<String>,
+    #[serde(default)]
+    pub windows: Vec<Window>,
+}
+
+/// Checks `name` follows the naming of built-in adjectives: lowercase ascii letters,
+/// digits and `-`.
+fn validate_name(name: &str) -> Result<(), String> {
+    if name.is_empty()
+        || name.len() > MAX_LENGTH
+        || name
+            .chars()
+            .any(|c| !matches!(c, 'a'..='z' | '0'..='9' | '-'))
+    {
+        return Err(format!(
+            "Invalid name `{name}`, expected lowercase ascii letters, digits and `-`"
+        ));
+    }
+    Ok(())
+}
+
+fn validate_length(value: &Option<String>) -> Result<(), String> {
+    match value {
+        Some(value) if value.chars().count() > MAX_LENGTH => Err(format!(
+            "Labels and descriptions must be at most {MAX_LENGTH} characters"
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Whether `c` can appear in an emoji after its first character: zero width joiners,
+/// variation selectors, skin tones, keycaps, tags and the emoji blocks themselves.
+fn is_emoji_char(c: char) -> bool {
+    matches!(c,
+        '\u{200D}'
+        | '\u{20E3}'
+        | '\u{FE0E}'..='\u{FE0F}'
+        | '\u{E0020}'..='\u{E007F}'
+        | '\u{00A9}'
+        | '\u{00AE}'
+        | '\u{203C}'..='\u{2BFF}'
+        | '\u{3030}'
+        | '\u{303D}'
+        | '\u{3297}'
+        | '\u{3299}'
+        | '\u{1F000}'..='\u{1FAFF}')
+}
+
+/// Checks `emoji` is a single unicode emoji, as select menus reject anything else.
+/// Custom server emojis are not supported.
+fn validate_emoji(emoji: &Option<String>) -> Result<(), String> {
+    let Some(emoji) = emoji else {
+        return Ok(());
+    };
+    let mut chars = emoji.chars();
+    let valid = match chars.next() {
+        // Keycaps such as 1️⃣ start with the ascii character they frame.
+        Some('0'..='9' | '#' | '*') => chars.as_str().ends_with('\u{20E3}'),
+        Some(first) => is_emoji_char(first) && first != '\u{200D}',
+        None => false,
+    };
+    // Sequences joining several emojis stay short; longer input is text or several emojis.
+    if !valid || emoji.chars().count() > 10 || !emoji.chars().skip(1).all(is_emoji_char) {
+        return Err("Expected a single unicode emoji".to_string());
+    }
+    Ok(())
+}
+
+impl AdjectiveSet {
+    pub fn new(guild_id: GuildId, name: &str, adjectives: &[&str]) -> Result<Self, String> {
+        validate_name(name)?;
+        if RESERVED.contains(&name) {
+            return Err(format!("`{name}` is a built-in set"));
+        }
+
+        let mut set = Self {
+            guild_id: guild_id.into(),
+            name: name.to_string(),
+            adjectives: Vec::new(),
+            retired: Vec::new(),
+            windows: Vec::new(),
+        };
+        for adjective in adjectives {
+            set.add(adjective)?;
+        }
+        if set.adjectives.is_empty() {
+            return Err("Expected at least one adjective".to_string());
+        }
+        Ok(set)
+    }
+
+    pub fn adjective(&self, name: &str) -> Option<&SetAdjective> {
+        self.adjectives
+            .iter()
+            .find(|adjective| adjective.name == name)
+    }
+
+    pub fn add(&mut self, name: &str) -> Result<(), String> {
+        validate_name(name)?;
+        if self.adjectives.len() >= MAX_ADJECTIVES {
+            return Err("This set has reached the maximum number of adjectives".to_string());
+        }
+        if self.adjective(name).is_some() {
+            return Err(format!("Duplicate adjective `{name}`"));
+        }
+        if self.retired.iter().any(|retired| retired == name) {
+            return Err(format!(
+                "Adjective `{name}` is retired and cannot be reused"
+            ));
+        }
+
+        self.adjectives.push(SetAdjective {
+            name: name.to_string(),
+            label: None,
+            description: None,
+            emoji: None,
+        });
+        Ok(())
+    }
+
+    /// Replaces the given metadata of adjective `name`, leaving `None` values unchanged.
+    pub fn edit(
+        &mut self,
+        name: &str,
+        label: Option<String>,
+        description: Option<String>,
+        emoji: Option<String>,
+    ) -> Result<(), String> {
+        validate_length(&label)?;
+        validate_length(&description)?;
+        validate_emoji(&emoji)?;
+        let adjective = self
+            .adjectives
+            .iter_mut()
+            .find(|adjective| adjective.name == name)
+            .ok_or_else(|| format!("Unknown adjective `{name}`"))?;
+
+        if label.is_some() {
+            adjective.label = label;
+        }
+        if description.is_some() {
+            adjective.description = description;
+        }
+        if emoji.is_some() {
+            adjective.emoji = emoji;
+        }
+        Ok(())
+    }
+
+    /// Removes adjective `name` from the set for good.
+    pub fn retire(&mut self, name: &str) -> Result<(), String> {
+        if self.adjectives.len() == 1 {
+            return Err("A set needs at least one adjective".to_string());
+        }
+        let index = self
+            .adjectives
+            .iter()
+            .position(|adjective| adjective.name == name)
+            .ok_or_else(|| format!("Unknown adjective `{name}`"))?;
+
+        self.adjectives.remove(index);
+        self.retired.push(name.to_string());
+        Ok(())
+    }
+
+    /// Labels of every adjective of the set, in order.
+    pub fn labels(&self) -> Vec<&str> {
+        self.adjectives.iter().map(SetAdjective::label).collect()
+    }
+
+    /// Labels of the adjectives in `selection` still part of the set, in set order.
+    pub fn labels_of(&self, selection: &[String]) -> Vec<&str> {
+        self.adjectives
+            .iter()
+            .filter(|adjective| selection.contains(&adjective.name))
+            .map(SetAdjective::label)
+            .collect()
+    }
+
+    pub fn window(&self, id: UserId) -> Option<&Window> {
+        self.windows
+            .iter()
+            .find(|window| window.id == u64::from(id))
+    }
+
+    pub fn window_mut(&mut self, id: UserId) -> Option<&mut Window> {
+        self.windows
+            .iter_mut()
+            .find(|window| window.id == u64::from(id))
+    }
+}
+
+#[derive(Default)]
+pub struct Sets(Vec<AdjectiveSet>);
+
+impl Sets {
+    pub fn load() -> io::Result<Self> {
+        Self::load_from(&store::path(STORE))
+    }
+
+    /// Loads the sets stored at `path`, e.g. a snapshot, an empty file loading no sets.
+    pub fn load_from(path: &Path) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        Self::parse(&contents).map_err(|error| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("cannot parse {}: {error}", path.display()),
+            )
+        })
+    }
+
+    /// Parses the stored sets, upgrading the bare array written before the file was
+    /// versioned (version 0).
+    fn parse(contents: &str) -> serde_json::Result<Self> {
+        if contents.trim().is_empty() {
+            return Ok(Self::default());
+        }
+
+        let mut value: serde_json::Value = serde_json::from_str(contents)?;
+        if value.is_array() {
+            value = serde_json::json!({ "version": 1, "sets": value });
+        }
+        match value["version"].as_u64() {
+            Some(VERSION) => Ok(Self(serde_json::from_value(value["sets"].take())?)),
+            Some(version) => Err(<serde_json::Error as serde::de::Error>::custom(format!(
+                "unsupported version {version}, expected {VERSION}"
+            ))),
+            None => Err(<serde_json::Error as serde::de::Error>::custom(
+                "missing version",
+            )),
+        }
+    }
+
+    pub fn dump(&self) -> io::Result<()> {
+        store::dump(
+            STORE,
+            &serde_json::json!({ "version": VERSION, "sets": &self.0 }),
+        )
+    }
+
+    /// Every set of every guild.
+    pub fn all(&self) -> &[AdjectiveSet] {
+        &self.0
+    }
+
+    /// The sets of guild `guild_id`.
+    pub fn of(&self, guild_id: GuildId) -> impl Iterator<Item = &AdjectiveSet> {
+        self.0
+            .iter()
+            .filter(move |set| set.guild_id == u64::from(guild_id))
+    }
+
+    pub fn get(&self, guild_id: GuildId, name: &str) -> Option<&AdjectiveSet> {
+        self.of(guild_id).find(|set| set.name == name)
+    }
+
+    pub fn get_mut(&mut self, guild_id: GuildId, name: &str) -> Option<&mut AdjectiveSet> {
+        self.0
+            .iter_mut()
+            .find(|set| set.guild_id == u64::from(guild_id) && set.name == name)
+    }
+
+    pub fn insert(&mut self, set: AdjectiveSet) -> Result<(), String> {
+        if self.get(GuildId::new(set.guild_id), &set.name).is_some() {
+            return Err(format!("The set `{}` already exists", set.name));
+        }
+        self.0.push(set);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set() -> AdjectiveSet {
+        AdjectiveSet::new(GuildId::new(1), "skills", &["rust", "public-speaking"]).unwrap()
+    }
+
+    #[test]
+    fn validates_names() {
+        assert!(AdjectiveSet::new(GuildId::new(1), "Skills", &["rust"]).is_err());
+        assert!(AdjectiveSet::new(GuildId::new(1), "johari", &["rust"]).is_err());
+        assert!(AdjectiveSet::new(GuildId::new(1), "skills", &[]).is_err());
+        assert!(AdjectiveSet::new(GuildId::new(1), "skills", &["rust", "rust"]).is_err());
+        assert!(set().add("team_work").is_err());
+    }
+
+    #[test]
+    fn retired_adjectives_cannot_be_reused() {
+        let mut set = set();
+        set.retire("rust").unwrap();
+        assert_eq!(set.labels(), vec!["public-speaking"]);
+        assert!(set.add("rust").is_err());
+        assert!(set.retire("public-speaking").is_err());
+    }
+
+    #[test]
+    fn edits_metadata() {
+        let mut set = set();
+        set.edit(
+            "rust",
+            Some("Rust".to_string()),
+            None,
+            Some("🦀".to_string()),
+        )
+        .unwrap();
+        set.edit("rust", None, Some("Writes Rust".to_string()), None)
+            .unwrap();
+
+        let rust = set.adjective("rust").unwrap();
+        assert_eq!(rust.label(), "Rust");
+        assert_eq!(rust.description.as_deref(), Some("Writes Rust"));
+        assert_eq!(rust.emoji.as_deref(), Some("🦀"));
+        assert!(set.edit("go", None, None, None).is_err());
+        assert!(set.edit("rust", Some("x".repeat(101)), None, None).is_err());
+    }
+
+    #[test]
+    fn validates_emojis() {
+        let mut set = set();
+        for emoji in ["🦀", "❤️", "1️⃣", "👩‍💻", "👍🏽", "🇩🇪"] {
+            assert!(
+                set.edit("rust", None, None, Some(emoji.to_string()))
+                    .is_ok(),
+                "{emoji}"
+            );
+        }
+        for emoji in ["", "x", "crab", "<:crab:1234>", "🦀 crab", "1", "あ"] {
+            assert!(
+                set.edit("rust", None, None, Some(emoji.to_string()))
+                    .is_err(),
+                "{emoji}"
+            );
+        }
+    }
+
+    #[test]
+    fn caps_the_adjectives_of_a_set() {
+        let mut set = set();
+        for index in set.adjectives.len()..MAX_ADJECTIVES {
+            set.add(&format!("skill-{index}")).unwrap();
+        }
+        assert!(set.add("one-too-many").is_err());
+    }
+
+    #[test]
+    fn upgrades_unversioned_sets() {
+        let sets =
+            Sets::parse(r#"[{"guild_id":1,"name":"skills","adjectives":[{"name":"rust"}]}]"#)
+                .unwrap();
+        assert_eq!(
+            sets.get(GuildId::new(1), "skills").unwrap().labels(),
+            vec!["rust"]
+        );
+        assert!(Sets::parse("").unwrap().all().is_empty());
+        assert!(Sets::parse(r#"{"version":2,"sets":[]}"#).is_err());
+    }
+
+    #[test]
+    fn removes_comments_by_number() {
+        let mut window = Window::new(1, vec!["rust".to_string()], None);
+        for (id, comment) in [
+            (2, Some("Kind")),
+            (3, None),
+            (4, Some("Calm")),
+            (5, Some("Witty")),
+        ] {
+            window.others.push(Window::new(
+                id,
+                vec!["rust".to_string()],
+                comment.map(str::to_string),
+            ));
+        }
+
+        assert_eq!(window.remove_comments(Some(2)), 1);
+        assert_eq!(window.comments(), vec!["Kind", "Witty"]);
+        assert_eq!(window.remove_comments(Some(3)), 0);
+        assert_eq!(window.remove_comments(None), 2);
+        assert!(window.comments().is_empty());
+    }
+
+    #[test]
+    fn labels_skip_retired_adjectives() {
+        let mut set = set();
+        set.edit("rust", Some("Rust".to_string()), None, None)
+            .unwrap();
+        let selection = vec!["public-speaking".to_string(), "rust".to_string()];
+        set.retire("public-speaking").unwrap();
+        assert_eq!(set.labels_of(&selection), vec!["Rust"]);
+    }
+}
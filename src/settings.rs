@@ -0,0 +1,105 @@
+use std::{io, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use serenity::{
+    model::prelude::{GuildId, UserId},
+    prelude::*,
+};
+
+use crate::store;
+
+pub const STORE: &str = "settings";
+
+/// Serializes changes to the settings store across handlers.
+pub struct SettingsLock;
+
+impl TypeMapKey for SettingsLock {
+    type Value = Arc<Mutex<()>>;
+}
+
+/// Moderation settings of a single guild.
+#[derive(Serialize, Deserialize)]
+pub struct GuildSettings {
+    pub id: u64,
+    /// Channel audit events are posted to.
+    #[serde(default)]
+    pub log_channel: Option<u64>,
+    /// Users who may not contribute to others' windows from this guild.
+    #[serde(default)]
+    pub banned: Vec<u64>,
+    /// Users who opted out of appearing in other members' similarity rankings.
+    #[serde(default)]
+    pub undiscoverable: Vec<u64>,
+    /// Users whose queries are only shown to them unless they ask otherwise.
+    #[serde(default)]
+    pub private_queries: Vec<u64>,
+    /// Whether contributions made from this guild can no longer carry comments, nor
+    /// comments be read in it.
+    #[serde(default)]
+    pub comments_disabled: bool,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(transparent)]
+pub struct Settings(Vec<GuildSettings>);
+
+impl Settings {
+    pub fn load() -> io::Result<Self> {
+        store::load(STORE)
+    }
+
+    pub fn dump(&self) -> io::Result<()> {
+        store::dump(STORE, self)
+    }
+
+    pub fn guild(&self, id: GuildId) -> Option<&GuildSettings> {
+        self.0.iter().find(|guild| guild.id == u64::from(id))
+    }
+
+    /// The settings of guild `id`, added with defaults if it has none yet.
+    pub fn guild_mut(&mut self, id: GuildId) -> &mut GuildSettings {
+        let id = u64::from(id);
+        match self.0.iter().position(|guild| guild.id == id) {
+            Some(index) => &mut self.0[index],
+            None => {
+                self.0.push(GuildSettings {
+                    id,
+                    log_channel: None,
+                    banned: Vec::new(),
+                    undiscoverable: Vec::new(),
+                    private_queries: Vec::new(),
+                    comments_disabled: false,
+                });
+                self.0.last_mut().unwrap()
+            }
+        }
+    }
+}
+
+pub fn is_banned(guild_id: GuildId, user_id: UserId) -> io::Result<bool> {
+    Ok(Settings::load()?
+        .guild(guild_id)
+        .is_some_and(|guild| guild.banned.contains(&u64::from(user_id))))
+}
+
+/// Whether `user_id` keeps their queries private by default in `guild_id`, never the
+/// case outside a guild.
+pub fn is_private(guild_id: Option<GuildId>, user_id: UserId) -> io::Result<bool> {
+    match guild_id {
+        Some(guild_id) => Ok(Settings::load()?
+            .guild(guild_id)
+            .is_some_and(|guild| guild.private_queries.contains(&u64::from(user_id)))),
+        None => Ok(false),
+    }
+}
+
+/// Whether comments can be left and read from `guild_id`, always the case outside a
+/// guild.
+pub fn comments_allowed(guild_id: Option<GuildId>) -> io::Result<bool> {
+    match guild_id {
+        Some(guild_id) => Ok(!Settings::load()?
+            .guild(guild_id)
+            .is_some_and(|guild| guild.comments_disabled)),
+        None => Ok(true),
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+/// A window reduced to what statistics are computed over: the labels the user chose
+/// and, for every contribution, the contributor's id and the labels they chose.
+pub struct Window<'a> {
+    pub own: Vec<&'a str>,
+    pub others: Vec<(u64, Vec<&'a str>)>,
+}
+
+/// Aggregates over every window of a store.
+#[derive(PartialEq, Debug)]
+pub struct Stats<'a> {
+    pub windows: usize,
+    pub contributions: usize,
+    /// How many self-assessments chose each adjective, most chosen first.
+    pub own_counts: Vec<(&'a str, usize)>,
+    /// How many peer contributions chose each adjective, most chosen first.
+    pub peer_counts: Vec<(&'a str, usize)>,
+    /// Share of adjectives chosen by peers that the user also chose, `None` without
+    /// any peer choice.
+    pub agreement: Option<f64>,
+    /// Contributor ids with how many windows they contributed to, most active first.
+    pub contributors: Vec<(u64, usize)>,
+}
+
+/// Counts every adjective of `adjectives` in `selections`, most chosen first and in list
+/// order among ties.
+fn counts<'a>(adjectives: &[&'a str], selections: Vec<&Vec<&str>>) -> Vec<(&'a str, usize)> {
+    let mut counts = adjectives
+        .iter()
+        .map(|adjective| (*adjective, 0))
+        .collect::<Vec<_>>();
+    for selection in selections {
+        for (adjective, count) in counts.iter_mut() {
+            if selection.contains(adjective) {
+                *count += 1;
+            }
+        }
+    }
+    counts.sort_by(|(_, a), (_, b)| b.cmp(a));
+    counts
+}
+
+pub fn aggregate<'a>(adjectives: &[&'a str], windows: &[Window]) -> Stats<'a> {
+    let mut agreeing = 0;
+    let mut peer_choices = 0;
+    let mut contributors = HashMap::new();
+
+    for window in windows {
+        for (contributor, other) in &window.others {
+            agreeing += other
+                .iter()
+                .filter(|label| window.own.contains(label))
+                .count();
+            peer_choices += other.len();
+            *contributors.entry(*contributor).or_insert(0) += 1;
+        }
+    }
+
+    let mut contributors = contributors.into_iter().collect::<Vec<_>>();
+    contributors.sort_by(|(a_id, a), (b_id, b)| b.cmp(a).then(a_id.cmp(b_id)));
+
+    Stats {
+        windows: windows.len(),
+        contributions: windows.iter().map(|window| window.others.len()).sum(),
+        own_counts: counts(
+            adjectives,
+            windows.iter().map(|window| &window.own).collect(),
+        ),
+        peer_counts: counts(
+            adjectives,
+            windows
+                .iter()
+                .flat_map(|window| window.others.iter().map(|(_, other)| other))
+                .collect(),
+        ),
+        agreement: (peer_choices > 0).then(|| agreeing as f64 / peer_choices as f64),
+        contributors,
+    }
+}
+
+/// Lists the first five of `counts` for an embed field.
+pub fn field<'b, 'c: 'b>(counts: impl Iterator<Item = &'b (&'c str, usize)>) -> String {
+    format!(
+        "```fix\n{}\n```",
+        counts
+            .take(5)
+            .map(|(adjective, count)| format!("{adjective} ({count})"))
+            .join("\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADJECTIVES: &[&str] = &["bold", "calm", "shy", "witty"];
+
+    #[test]
+    fn aggregates_windows() {
+        let windows = [
+            Window {
+                own: vec!["bold", "calm"],
+                others: vec![(2, vec!["bold", "shy"]), (3, vec!["calm"])],
+            },
+            Window {
+                own: vec!["calm"],
+                others: vec![(3, vec!["witty"])],
+            },
+        ];
+
+        let stats = aggregate(ADJECTIVES, &windows);
+        assert_eq!(stats.windows, 2);
+        assert_eq!(stats.contributions, 3);
+        assert_eq!(
+            stats.own_counts,
+            vec![("calm", 2), ("bold", 1), ("shy", 0), ("witty", 0)]
+        );
+        assert_eq!(
+            stats.peer_counts,
+            vec![("bold", 1), ("calm", 1), ("shy", 1), ("witty", 1)]
+        );
+        // bold and calm agree out of four peer choices.
+        assert_eq!(stats.agreement, Some(0.5));
+        assert_eq!(stats.contributors, vec![(3, 2), (2, 1)]);
+    }
+
+    #[test]
+    fn empty_store_has_no_agreement() {
+        let stats = aggregate(ADJECTIVES, &[]);
+        assert_eq!(stats.windows, 0);
+        assert_eq!(stats.agreement, None);
+        assert!(stats.contributors.is_empty());
+        assert!(stats.own_counts.iter().all(|(_, count)| *count == 0));
+    }
+}
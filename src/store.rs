@@ -0,0 +1,380 @@
+use std::{
+    collections::HashMap,
+    env, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+const BACKUP_DIRECTORY: &str = "backups";
+/// Snapshots are taken on write, but at most once per interval per store.
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(60 * 60);
+/// Number of snapshots kept per store; older ones are deleted.
+const MAX_SNAPSHOTS: usize = 72;
+
+/// The directory stores are kept in, `SOSHARI_DATA` or the working directory by default.
+pub fn data_directory() -> PathBuf {
+    env::var_os("SOSHARI_DATA")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+fn backup_directory() -> PathBuf {
+    data_directory().join(BACKUP_DIRECTORY)
+}
+
+/// The file backing the store named `store`, e.g. `johari.json`.
+pub fn path(store: &str) -> PathBuf {
+    data_directory().join(format!("{store}.json"))
+}
+
+fn with_path(path: &Path, error: io::Error) -> io::Error {
+    io::Error::new(error.kind(), format!("{}: {error}", path.display()))
+}
+
+/// Checks that new files can be created and removed in `directory`, which writing a
+/// store (through a temporary file) and taking snapshots rely on.
+fn check_writable(directory: &Path) -> io::Result<()> {
+    let probe = directory.join(".soshari-probe");
+    fs::write(&probe, b"").map_err(|error| with_path(directory, error))?;
+    fs::remove_file(&probe).map_err(|error| with_path(directory, error))
+}
+
+/// Prepares the data directory at startup: creates it along with the backup directory
+/// and any missing store, then checks every store can be read and written.
+pub fn bootstrap(stores: &[&str]) -> io::Result<()> {
+    for directory in [data_directory(), backup_directory()] {
+        fs::create_dir_all(&directory).map_err(|error| with_path(&directory, error))?;
+        check_writable(&directory)?;
+    }
+
+    for store in stores {
+        let path = path(store);
+        // An empty file loads as an empty store.
+        fs::OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&path)
+            .map_err(|error| with_path(&path, error))?;
+    }
+
+    Ok(())
+}
+
+/// Reads the raw contents of the store named `store`.
+pub fn read(store: &str) -> io::Result<String> {
+    let path = path(store);
+    fs::read_to_string(&path).map_err(|error| with_path(&path, error))
+}
+
+/// Reads the store named `store`, an empty file loading as `T::default()`.
+pub fn load<T: DeserializeOwned + Default>(store: &str) -> io::Result<T> {
+    let contents = read(store)?;
+    if contents.trim().is_empty() {
+        return Ok(T::default());
+    }
+    serde_json::from_str(&contents).map_err(|error| with_path(&path(store), error.into()))
+}
+
+/// Writes `value` to the store named `store` through a temporary file renamed over it.
+pub fn dump<T: Serialize>(store: &str, value: &T) -> io::Result<()> {
+    write(store, serde_json::to_string_pretty(value)?.as_bytes())
+}
+
+/// Replaces the contents of the store named `store` through a temporary file renamed
+/// over it.
+pub fn write(store: &str, contents: &[u8]) -> io::Result<()> {
+    let path = path(store);
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+
+    let mut file = fs::File::create(&temporary)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    fs::rename(&temporary, path)
+}
+
+/// Appends `contents` to the store named `store` without rewriting what it holds.
+pub fn append(store: &str, contents: &[u8]) -> io::Result<()> {
+    let path = path(store);
+    let mut file = fs::OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .map_err(|error| with_path(&path, error))?;
+    file.write_all(contents)?;
+    file.sync_all()
+}
+
+/// A point-in-time copy of a store, named `<store>-<unix timestamp>`, followed by
+/// `.<sequence>` for further snapshots taken within the same second.
+pub struct Snapshot {
+    pub name: String,
+    pub store: String,
+    pub timestamp: u64,
+    sequence: u64,
+    /// The backup directory the snapshot is kept in.
+    directory: PathBuf,
+}
+
+impl Snapshot {
+    fn parse(path: &Path) -> Option<Self> {
+        if path.extension()? != "json" {
+            return None;
+        }
+        let name = path.file_stem()?.to_str()?;
+        let (store, suffix) = name.rsplit_once('-')?;
+        let (timestamp, sequence) = match suffix.split_once('.') {
+            Some((timestamp, sequence)) => (timestamp, sequence.parse().ok()?),
+            None => (suffix, 0),
+        };
+
+        Some(Self {
+            name: name.to_string(),
+            store: store.to_string(),
+            timestamp: timestamp.parse().ok()?,
+            sequence,
+            directory: path.parent()?.to_path_buf(),
+        })
+    }
+
+    pub fn path(&self) -> PathBuf {
+        self.directory.join(format!("{}.json", self.name))
+    }
+}
+
+fn store_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Seconds since the unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+/// Lists the snapshots of every store, oldest first.
+pub fn snapshots() -> io::Result<Vec<Snapshot>> {
+    snapshots_in(&backup_directory())
+}
+
+fn snapshots_in(directory: &Path) -> io::Result<Vec<Snapshot>> {
+    let mut snapshots = match fs::read_dir(directory) {
+        Ok(entries) => entries
+            .filter_map(|entry| Snapshot::parse(&entry.ok()?.path()))
+            .collect::<Vec<_>>(),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Vec::new(),
+        Err(error) => return Err(error),
+    };
+    snapshots.sort_by_key(|snapshot| (snapshot.timestamp, snapshot.sequence));
+    Ok(snapshots)
+}
+
+pub fn snapshot(name: &str) -> io::Result<Option<Snapshot>> {
+    Ok(snapshots()?
+        .into_iter()
+        .find(|snapshot| snapshot.name == name))
+}
+
+/// Copies the store at `path` into the backup directory if its latest snapshot is older
+/// than [`SNAPSHOT_INTERVAL`] (or unconditionally when `force` is set), then prunes the
+/// store's snapshots down to [`MAX_SNAPSHOTS`].
+pub fn backup<P: AsRef<Path>>(path: P, force: bool) -> io::Result<()> {
+    backup_to(path.as_ref(), &backup_directory(), force)
+}
+
+/// Like [`backup`], keeping the snapshots in `directory`.
+fn backup_to(path: &Path, directory: &Path, force: bool) -> io::Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let store = store_name(path);
+    let mut snapshots = snapshots_in(directory)?
+        .into_iter()
+        .filter(|snapshot| snapshot.store == store)
+        .collect::<Vec<_>>();
+
+    let timestamp = now();
+    let is_recent = snapshots.last().is_some_and(|latest| {
+        timestamp.saturating_sub(latest.timestamp) < SNAPSHOT_INTERVAL.as_secs()
+    });
+
+    if force || !is_recent {
+        let sequence = snapshots
+            .iter()
+            .filter(|snapshot| snapshot.timestamp == timestamp)
+            .map(|snapshot| snapshot.sequence + 1)
+            .max()
+            .unwrap_or_default();
+        let snapshot = Snapshot {
+            name: match sequence {
+                0 => format!("{store}-{timestamp}"),
+                sequence => format!("{store}-{timestamp}.{sequence}"),
+            },
+            store,
+            timestamp,
+            sequence,
+            directory: directory.to_path_buf(),
+        };
+        fs::create_dir_all(directory)?;
+        fs::copy(path, snapshot.path())?;
+        snapshots.push(snapshot);
+    }
+
+    let expired = snapshots.len().saturating_sub(MAX_SNAPSHOTS);
+    for snapshot in &snapshots[..expired] {
+        fs::remove_file(snapshot.path())?;
+    }
+
+    Ok(())
+}
+
+/// Windows that differ between the current store and a snapshot, by user id.
+#[derive(Default)]
+pub struct Diff {
+    pub added: Vec<u64>,
+    pub removed: Vec<u64>,
+    pub changed: Vec<u64>,
+}
+
+impl Diff {
+    /// Describes what restoring `snapshot` over `current` would do.
+    pub fn new<T: Serialize>(current: &[T], snapshot: &[T]) -> Self {
+        let by_id = |windows: &[T]| -> HashMap<u64, serde_json::Value> {
+            windows
+                .iter()
+                .filter_map(|window| {
+                    let value = serde_json::to_value(window).ok()?;
+                    Some((value["id"].as_u64()?, value))
+                })
+                .collect()
+        };
+        let current = by_id(current);
+        let snapshot = by_id(snapshot);
+
+        let mut diff = Self::default();
+        for (id, window) in &snapshot {
+            match current.get(id) {
+                None => diff.added.push(*id),
+                Some(current) if current != window => diff.changed.push(*id),
+                Some(_) => {}
+            }
+        }
+        diff.removed = current
+            .keys()
+            .filter(|id| !snapshot.contains_key(id))
+            .copied()
+            .collect();
+
+        diff.added.sort_unstable();
+        diff.removed.sort_unstable();
+        diff.changed.sort_unstable();
+        diff
+    }
+
+    /// Adds the windows of `other`, listing each user once per kind of change.
+    pub fn extend(&mut self, other: Diff) {
+        for (ids, others) in [
+            (&mut self.added, other.added),
+            (&mut self.removed, other.removed),
+            (&mut self.changed, other.changed),
+        ] {
+            ids.extend(others);
+            ids.sort_unstable();
+            ids.dedup();
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Window {
+        id: u64,
+        adjectives: Vec<&'static str>,
+    }
+
+    fn window(id: u64, adjectives: &[&'static str]) -> Window {
+        Window {
+            id,
+            adjectives: adjectives.to_vec(),
+        }
+    }
+
+    #[test]
+    fn diffs_windows_by_id() {
+        let current = [
+            window(1, &["bold"]),
+            window(2, &["calm"]),
+            window(3, &["shy"]),
+        ];
+        let snapshot = [
+            window(4, &["witty"]),
+            window(2, &["bold"]),
+            window(1, &["bold"]),
+        ];
+
+        let diff = Diff::new(&current, &snapshot);
+        assert_eq!(diff.added, vec![4]);
+        assert_eq!(diff.removed, vec![3]);
+        assert_eq!(diff.changed, vec![2]);
+        assert!(Diff::new(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn parses_snapshot_names() {
+        let snapshot = Snapshot::parse(Path::new("backups/johari-1700000000.json")).unwrap();
+        assert_eq!(snapshot.name, "johari-1700000000");
+        assert_eq!(snapshot.store, "johari");
+        assert_eq!((snapshot.timestamp, snapshot.sequence), (1_700_000_000, 0));
+
+        let snapshot = Snapshot::parse(Path::new("picker-sessions-1700000000.2.json")).unwrap();
+        assert_eq!(snapshot.store, "picker-sessions");
+        assert_eq!((snapshot.timestamp, snapshot.sequence), (1_700_000_000, 2));
+
+        assert!(Snapshot::parse(Path::new("johari.json")).is_none());
+        assert!(Snapshot::parse(Path::new("johari-1700000000.json.tmp")).is_none());
+        assert!(Snapshot::parse(Path::new("johari-latest.json")).is_none());
+        assert!(Snapshot::parse(Path::new("johari-1700000000.x.json")).is_none());
+    }
+
+    #[test]
+    fn prunes_snapshots_taken_in_the_same_second() {
+        let directory = env::temp_dir().join(format!("soshari-backups-{}", std::process::id()));
+        let backups = directory.join(BACKUP_DIRECTORY);
+        let store = directory.join("johari.json");
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(&store, "[]").unwrap();
+
+        for _ in 0..MAX_SNAPSHOTS + 3 {
+            backup_to(&store, &backups, true).unwrap();
+        }
+        let taken = snapshots_in(&backups).unwrap();
+        assert_eq!(taken.len(), MAX_SNAPSHOTS);
+        assert!(taken
+            .windows(2)
+            .all(|pair| (pair[0].timestamp, pair[0].sequence)
+                < (pair[1].timestamp, pair[1].sequence)));
+
+        // Snapshots are only taken once per interval unless forced.
+        backup_to(&store, &backups, false).unwrap();
+        assert_eq!(snapshots_in(&backups).unwrap().len(), MAX_SNAPSHOTS);
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
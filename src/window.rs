@@ -0,0 +1,302 @@
+use std::fmt;
+
+use itertools::Itertools;
+
+/// Discord's limit on embed field values.
+pub const MAX_FIELD_LENGTH: usize = 1024;
+
+/// The four panes of a johari window.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Quadrant {
+    /// Chosen by the user and at least one peer.
+    Arena,
+    /// Chosen by peers but not by the user.
+    Blind,
+    /// Chosen by the user but by no peer.
+    Facade,
+    /// Chosen by nobody.
+    Unknown,
+}
+
+impl fmt::Display for Quadrant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Quadrant::Arena => "Arena",
+            Quadrant::Blind => "Blind",
+            Quadrant::Facade => "Facade",
+            Quadrant::Unknown => "Unknown",
+        })
+    }
+}
+
+/// Where an adjective falls in a window, and how many peers chose it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Placement<'a> {
+    pub adjective: &'a str,
+    pub quadrant: Quadrant,
+    pub count: usize,
+}
+
+/// Places each of `adjectives` in its quadrant given the user's own choice and every
+/// peer's, keeping the order of `adjectives`.
+pub fn place<'a>(adjectives: &[&'a str], own: &[&str], others: &[Vec<&str>]) -> Vec<Placement<'a>> {
+    adjectives
+        .iter()
+        .map(|adjective| {
+            let count = others
+                .iter()
+                .filter(|other| other.contains(adjective))
+                .count();
+            let quadrant = match (own.contains(adjective), count > 0) {
+                (true, true) => Quadrant::Arena,
+                (false, true) => Quadrant::Blind,
+                (true, false) => Quadrant::Facade,
+                (false, false) => Quadrant::Unknown,
+            };
+
+            Placement {
+                adjective,
+                quadrant,
+                count,
+            }
+        })
+        .collect()
+}
+
+/// Lists the adjectives placed in `quadrant` for an embed field, with how many peers
+/// chose each one when more than one did.
+pub fn field(placements: &[Placement], quadrant: Quadrant) -> String {
+    let adjectives = placements
+        .iter()
+        .filter(|placement| placement.quadrant == quadrant)
+        .map(|placement| {
+            if placement.count > 1 {
+                format!("{} ({})", placement.adjective, placement.count)
+            } else {
+                placement.adjective.to_string()
+            }
+        })
+        .collect::<Vec<_>>();
+    // Leaves room for the code block around the list.
+    let adjectives = lines(&adjectives, MAX_FIELD_LENGTH - "```fix\n\n```".len());
+    if adjectives.is_empty() {
+        "N/A".to_string()
+    } else {
+        format!("```fix\n{adjectives}\n```")
+    }
+}
+
+/// Joins `lines` one per line, ending with "and N more" instead of the lines that would
+/// not fit in `max_length` characters.
+pub fn lines(lines: &[String], max_length: usize) -> String {
+    let joined = lines.join("\n");
+    if joined.chars().count() <= max_length {
+        return joined;
+    }
+
+    // Room for the last line, however many lines are left out.
+    let mut length = "\nand 9999 more".len();
+    let shown = lines
+        .iter()
+        .take_while(|line| {
+            length += line.chars().count() + 1;
+            length <= max_length
+        })
+        .count();
+    format!(
+        "{}\nand {} more",
+        lines[..shown].join("\n"),
+        lines.len() - shown
+    )
+}
+
+/// Jaccard similarity of two selections: the adjectives both chose over those either
+/// chose, `None` when both are empty.
+pub fn jaccard(a: &[&str], b: &[&str]) -> Option<f64> {
+    let shared = a.iter().filter(|adjective| b.contains(adjective)).count();
+    let either = a.len() + b.len() - shared;
+    (either > 0).then(|| shared as f64 / either as f64)
+}
+
+/// How closely a user's self-assessment matches how peers see them.
+#[derive(PartialEq, Debug)]
+pub struct Agreement {
+    /// Similarity with every adjective any peer chose, `None` without any choice.
+    pub overall: Option<f64>,
+    /// Similarity with each peer's choice, in the order of the contributions.
+    pub peers: Vec<Option<f64>>,
+}
+
+impl Agreement {
+    /// The mean agreement of the peers, `None` without any peer.
+    pub fn mean(&self) -> Option<f64> {
+        let peers = self.peers.iter().flatten().collect::<Vec<_>>();
+        (!peers.is_empty()).then(|| peers.iter().copied().sum::<f64>() / peers.len() as f64)
+    }
+}
+
+/// Computes the self-awareness of a window from the user's own choice and every peer's.
+pub fn agreement(own: &[&str], others: &[Vec<&str>]) -> Agreement {
+    let combined = others
+        .iter()
+        .flatten()
+        .copied()
+        .unique()
+        .collect::<Vec<_>>();
+
+    Agreement {
+        overall: if others.is_empty() {
+            None
+        } else {
+            jaccard(own, &combined)
+        },
+        peers: others.iter().map(|other| jaccard(own, other)).collect(),
+    }
+}
+
+/// An adjective that changed quadrant between two placements of the same window.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Movement<'a> {
+    pub adjective: &'a str,
+    pub from: Quadrant,
+    pub to: Quadrant,
+}
+
+impl fmt::Display for Movement<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} → {}", self.adjective, self.from, self.to)
+    }
+}
+
+/// The adjectives whose quadrant differs between `before` and `after`, both placed
+/// from the same adjective list.
+pub fn movements<'a>(before: &[Placement<'a>], after: &[Placement<'a>]) -> Vec<Movement<'a>> {
+    before
+        .iter()
+        .zip(after)
+        .filter(|(before, after)| before.quadrant != after.quadrant)
+        .map(|(before, after)| Movement {
+            adjective: after.adjective,
+            from: before.quadrant,
+            to: after.quadrant,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADJECTIVES: &[&str] = &["bold", "calm", "shy", "witty"];
+
+    #[test]
+    fn places_adjectives_in_quadrants() {
+        let placements = place(
+            ADJECTIVES,
+            &["bold", "calm"],
+            &[vec!["bold", "shy"], vec!["shy"]],
+        );
+
+        assert_eq!(
+            placements,
+            vec![
+                Placement {
+                    adjective: "bold",
+                    quadrant: Quadrant::Arena,
+                    count: 1,
+                },
+                Placement {
+                    adjective: "calm",
+                    quadrant: Quadrant::Facade,
+                    count: 0,
+                },
+                Placement {
+                    adjective: "shy",
+                    quadrant: Quadrant::Blind,
+                    count: 2,
+                },
+                Placement {
+                    adjective: "witty",
+                    quadrant: Quadrant::Unknown,
+                    count: 0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_moved_adjectives() {
+        let before = place(ADJECTIVES, &["calm"], &[vec!["bold"]]);
+        let after = place(
+            ADJECTIVES,
+            &["bold", "calm"],
+            &[vec!["bold"], vec!["witty"]],
+        );
+
+        let moved = movements(&before, &after);
+        assert_eq!(
+            moved,
+            vec![
+                Movement {
+                    adjective: "bold",
+                    from: Quadrant::Blind,
+                    to: Quadrant::Arena,
+                },
+                Movement {
+                    adjective: "witty",
+                    from: Quadrant::Unknown,
+                    to: Quadrant::Blind,
+                },
+            ]
+        );
+        assert_eq!(moved[0].to_string(), "bold: Blind → Arena");
+    }
+
+    #[test]
+    fn computes_jaccard_similarity() {
+        assert_eq!(
+            jaccard(&["bold", "calm"], &["calm", "shy"]),
+            Some(1.0 / 3.0)
+        );
+        assert_eq!(jaccard(&["bold"], &["bold"]), Some(1.0));
+        assert_eq!(jaccard(&["bold"], &["shy"]), Some(0.0));
+        assert_eq!(jaccard(&[], &[]), None);
+    }
+
+    #[test]
+    fn computes_agreement() {
+        let agreement = agreement(
+            &["bold", "calm"],
+            &[vec!["bold"], vec!["calm", "shy", "witty"]],
+        );
+
+        // Combined, peers chose all four adjectives, two of which the user chose.
+        assert_eq!(agreement.overall, Some(0.5));
+        assert_eq!(agreement.peers, vec![Some(0.5), Some(0.25)]);
+        assert_eq!(agreement.mean(), Some(0.375));
+    }
+
+    #[test]
+    fn window_without_peers_has_no_agreement() {
+        let agreement = agreement(&["bold"], &[]);
+        assert_eq!(agreement.overall, None);
+        assert_eq!(agreement.mean(), None);
+    }
+
+    #[test]
+    fn unchanged_window_has_no_movements() {
+        let placements = place(ADJECTIVES, &["shy"], &[vec!["shy", "calm"]]);
+        assert!(movements(&placements, &placements).is_empty());
+    }
+
+    #[test]
+    fn shortens_long_fields() {
+        let short = vec!["bold".to_string(), "calm".to_string()];
+        assert_eq!(lines(&short, MAX_FIELD_LENGTH), "bold\ncalm");
+
+        let long = (0..120).map(|i| format!("{i:0>99}")).collect::<Vec<_>>();
+        let field = lines(&long, MAX_FIELD_LENGTH);
+        assert!(field.chars().count() <= MAX_FIELD_LENGTH);
+        assert!(field.ends_with("\nand 110 more"));
+    }
+}